@@ -0,0 +1,209 @@
+use p3_challenger::{DuplexChallenger, FieldChallenger, GrindingChallenger};
+use p3_field::{ExtensionField, PrimeCharacteristicRing, extension::BinomialExtensionField};
+use p3_koala_bear::{KoalaBear, Poseidon2KoalaBear, default_koalabear_poseidon2_16};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Challenger configurations that can be selected at runtime (see [`AnyVerifierState`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChallengerKind {
+    /// `DuplexChallenger` over the KoalaBear Poseidon2 permutation of width 16.
+    Poseidon2KoalaBear,
+    /// `keccak_challenger`.
+    KeccakKoalaBear,
+    /// `sha256_challenger`.
+    Sha256KoalaBear,
+    /// `blake3_challenger`.
+    Blake3KoalaBear,
+    /// `EvmChallenger` over KoalaBear.
+    EvmKoalaBear,
+}
+
+/// Configuration header of a [`Proof`], recorded by the prover.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProofConfig {
+    /// Challenger used by the prover, if it is one of the [`ChallengerKind`]s.
+    pub challenger: Option<ChallengerKind>,
+    /// Degree of the extension field challenges are sampled from.
+    pub extension_degree: usize,
+}
+
+/// A proof submitted to an [`AnyVerifierState`], with the transcript context it must have been
+/// created in.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TranscriptBinding<'a> {
+    /// A proof created with `ProverState::new_with_protocol`.
+    Protocol {
+        proof: Proof<KoalaBear>,
+        protocol_id: ProtocolId,
+    },
+    /// A signature of knowledge created with `ProverState::new_signing`.
+    Signature {
+        signature: Signature<KoalaBear>,
+        public_key: &'a [u8],
+        message: &'a [u8],
+    },
+}
+
+/// Context absorbed at the start of the transcript, once the proof is decoded.
+#[derive(Clone, Copy)]
+enum TranscriptContext<'a> {
+    Protocol(ProtocolId),
+    Signature {
+        public_key: &'a [u8],
+        message: &'a [u8],
+    },
+}
+
+impl<'a> TranscriptBinding<'a> {
+    /// Decode the proof (signatures are decoded within `limits`) and split off its context.
+    fn into_proof(
+        self,
+        limits: &VerifierLimits,
+    ) -> ProofResult<(Proof<KoalaBear>, TranscriptContext<'a>)> {
+        match self {
+            Self::Protocol { proof, protocol_id } => {
+                Ok((proof, TranscriptContext::Protocol(protocol_id)))
+            }
+            Self::Signature {
+                signature,
+                public_key,
+                message,
+            } => Ok((
+                signature.proof.into_proof(limits)?,
+                TranscriptContext::Signature {
+                    public_key,
+                    message,
+                },
+            )),
+        }
+    }
+}
+
+impl TranscriptContext<'_> {
+    /// Create a verifier state for `proof` enforcing `limits`, bound to this context.
+    fn verifier_state<EF, Challenger>(
+        self,
+        proof: Proof<KoalaBear>,
+        challenger: Challenger,
+        limits: VerifierLimits,
+    ) -> ProofResult<VerifierState<KoalaBear, EF, Challenger>>
+    where
+        EF: ExtensionField<KoalaBear>,
+        Challenger:
+            FieldChallenger<KoalaBear> + GrindingChallenger<Witness = KoalaBear> + ChallengerState,
+    {
+        match self {
+            Self::Protocol(protocol_id) => {
+                VerifierState::new_with_protocol_id(proof, challenger, protocol_id, limits)
+            }
+            Self::Signature {
+                public_key,
+                message,
+            } => {
+                let mut state = VerifierState::new_with_limits(proof, challenger, limits)?;
+                state.observe_signing_context(public_key, message);
+                Ok(state)
+            }
+        }
+    }
+}
+
+/// Verification code generic over the configuration, run by [`AnyVerifierState::visit`].
+pub trait VerifierVisitor {
+    type Output;
+
+    fn visit<EF, Challenger>(
+        self,
+        verifier: &mut VerifierState<KoalaBear, EF, Challenger>,
+    ) -> Self::Output
+    where
+        EF: ExtensionField<KoalaBear> + PrimeCharacteristicRing<PrimeSubfield = KoalaBear>,
+        Challenger:
+            FieldChallenger<KoalaBear> + GrindingChallenger<Witness = KoalaBear> + ChallengerState;
+}
+
+macro_rules! any_verifier_state {
+    ($($variant:ident => ($kind:ident, $degree:literal, $challenger:ty, $new_challenger:expr)),* $(,)?) => {
+        /// A KoalaBear verifier state whose challenger and extension field are selected at runtime
+        /// from the configuration header of the proof, so that a single endpoint can verify
+        /// several proof flavors (padded or not, Poseidon2 or hash-based challengers, extension
+        /// degree 4 or 8).
+        pub enum AnyVerifierState {
+            $($variant(Box<VerifierState<KoalaBear, BinomialExtensionField<KoalaBear, $degree>, $challenger>>),)*
+        }
+
+        impl AnyVerifierState {
+            /// Instantiate the verifier state matching the configuration header of the submitted
+            /// proof.
+            ///
+            /// The header is chosen by the prover, so only the configurations in `allowed`
+            /// are accepted, and the transcript is bound to the context of `binding` as the
+            /// prover's was. The proof is checked against `limits` before any hashing, and
+            /// each subsequent read is checked against the same limits.
+            ///
+            /// # Arguments
+            /// - `binding`: The proof or signature, and the context it must have been created in.
+            /// - `allowed`: Accepted `(challenger, extension degree)` configurations.
+            /// - `limits`: Resource limits enforced on the proof.
+            ///
+            /// # Errors
+            /// Returns `ProofError::LimitExceeded` if the proof exceeds `limits`,
+            /// `ProofError::InvalidProof` if a signature encoding is malformed,
+            /// `ProofError::UnsupportedConfig` if the configuration is not allowed or not
+            /// supported, `ProofError::ProtocolMismatch` if the proof was made for a different
+            /// protocol, or `ProofError::PaddingMismatch` if the proof was padded to a different
+            /// vector length.
+            pub fn new(
+                binding: TranscriptBinding<'_>,
+                allowed: &[(ChallengerKind, usize)],
+                limits: VerifierLimits,
+            ) -> ProofResult<Self> {
+                let (proof, context) = binding.into_proof(&limits)?;
+                limits.check_proof(&proof)?;
+                let ProofConfig { challenger, extension_degree } = proof.config;
+                if !challenger.is_some_and(|kind| allowed.contains(&(kind, extension_degree))) {
+                    return Err(ProofError::UnsupportedConfig);
+                }
+                match (challenger, extension_degree) {
+                    $((Some(ChallengerKind::$kind), $degree) => Ok(Self::$variant(Box::new(
+                        context.verifier_state(proof, $new_challenger, limits)?,
+                    ))),)*
+                    _ => Err(ProofError::UnsupportedConfig),
+                }
+            }
+
+            /// Run `visitor` on the underlying verifier state.
+            pub fn visit<V: VerifierVisitor>(&mut self, visitor: V) -> V::Output {
+                match self {
+                    $(Self::$variant(state) => visitor.visit(state.as_mut()),)*
+                }
+            }
+
+            /// End the verification, checking that the whole proof was consumed
+            /// (see `VerifierState::finish`).
+            ///
+            /// # Errors
+            /// Returns the error of `VerifierState::finish`.
+            pub fn finish(self) -> ProofResult<()> {
+                match self {
+                    $(Self::$variant(state) => state.finish(),)*
+                }
+            }
+        }
+    };
+}
+
+any_verifier_state! {
+    Poseidon2Degree4 => (Poseidon2KoalaBear, 4, DuplexChallenger<KoalaBear, Poseidon2KoalaBear<16>, 16, 8>, DuplexChallenger::new(default_koalabear_poseidon2_16())),
+    Poseidon2Degree8 => (Poseidon2KoalaBear, 8, DuplexChallenger<KoalaBear, Poseidon2KoalaBear<16>, 16, 8>, DuplexChallenger::new(default_koalabear_poseidon2_16())),
+    KeccakDegree4 => (KeccakKoalaBear, 4, KoalaBearKeccakChallenger, keccak_challenger()),
+    KeccakDegree8 => (KeccakKoalaBear, 8, KoalaBearKeccakChallenger, keccak_challenger()),
+    Sha256Degree4 => (Sha256KoalaBear, 4, KoalaBearSha256Challenger, sha256_challenger()),
+    Sha256Degree8 => (Sha256KoalaBear, 8, KoalaBearSha256Challenger, sha256_challenger()),
+    Blake3Degree4 => (Blake3KoalaBear, 4, KoalaBearBlake3Challenger, blake3_challenger()),
+    Blake3Degree8 => (Blake3KoalaBear, 8, KoalaBearBlake3Challenger, blake3_challenger()),
+    EvmDegree4 => (EvmKoalaBear, 4, KoalaBearEvmChallenger, KoalaBearEvmChallenger::new()),
+    EvmDegree8 => (EvmKoalaBear, 8, KoalaBearEvmChallenger, KoalaBearEvmChallenger::new()),
+}
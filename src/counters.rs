@@ -0,0 +1,113 @@
+use std::{
+    collections::BTreeMap,
+    ops::AddAssign,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use p3_challenger::DuplexChallenger;
+use p3_field::Field;
+use p3_koala_bear::{KoalaBear, Poseidon2KoalaBear};
+use p3_symmetric::{CryptographicPermutation, Permutation};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Cost of a sequence of transcript operations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TranscriptCounts {
+    /// Permutations performed by the challenger (only counted if the challenger tracks them,
+    /// see `ChallengerState::n_permutations`).
+    pub permutations: usize,
+    /// Base field elements observed by the challenger (including padding zeros).
+    pub observed: usize,
+    /// Base field elements squeezed from the challenger.
+    pub squeezed: usize,
+    /// Grinding witnesses checked. On the prover side, this is estimated by the number of
+    /// permutations spent grinding (one per candidate witness).
+    pub grinding_attempts: usize,
+}
+
+impl AddAssign for TranscriptCounts {
+    fn add_assign(&mut self, rhs: Self) {
+        self.permutations += rhs.permutations;
+        self.observed += rhs.observed;
+        self.squeezed += rhs.squeezed;
+        self.grinding_attempts += rhs.grinding_attempts;
+    }
+}
+
+/// Transcript costs accumulated by a prover or verifier, in total and by label.
+///
+/// Labels are set with `set_label` on `ProverState` / `VerifierState`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptCounters {
+    pub total: TranscriptCounts,
+    pub by_label: BTreeMap<String, TranscriptCounts>,
+}
+
+impl TranscriptCounters {
+    pub(crate) fn record(&mut self, label: Option<&str>, counts: TranscriptCounts) {
+        self.total += counts;
+        if let Some(label) = label {
+            *self.by_label.entry(label.to_string()).or_default() += counts;
+        }
+    }
+}
+
+/// A permutation counting its calls.
+///
+/// The counter is shared between clones, so that permutations performed on copies of the
+/// challenger (e.g. while grinding) are counted as well.
+#[derive(Clone, Debug, Default)]
+pub struct CountingPermutation<P> {
+    inner: P,
+    count: Arc<AtomicUsize>,
+}
+
+impl<P> CountingPermutation<P> {
+    #[must_use]
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            count: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Number of permutations performed so far.
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+impl<T: Clone, P: Permutation<T>> Permutation<T> for CountingPermutation<P> {
+    fn permute_mut(&self, input: &mut T) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.inner.permute_mut(input);
+    }
+}
+
+impl<T: Clone, P: CryptographicPermutation<T>> CryptographicPermutation<T>
+    for CountingPermutation<P>
+{
+}
+
+pub type KoalaBearCountingChallenger =
+    DuplexChallenger<KoalaBear, CountingPermutation<Poseidon2KoalaBear<16>>, 16, 8>;
+
+impl<F, P, const WIDTH: usize, const RATE: usize> ChallengerState
+    for DuplexChallenger<F, CountingPermutation<P>, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    fn state(&self) -> String {
+        format!("{:?}", self.sponge_state)
+    }
+
+    fn n_permutations(&self) -> Option<usize> {
+        Some(self.permutation.count())
+    }
+}
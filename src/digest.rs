@@ -0,0 +1,68 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+};
+
+/// Digest length used when none is specified (8 KoalaBear elements, i.e. one lean ISA vector).
+pub const DEFAULT_DIGEST_LEN: usize = 8;
+
+/// A Merkle digest (commitment root or authentication path node) made of `N` field elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Digest<F, const N: usize>(pub [F; N]);
+
+impl<F, const N: usize> From<[F; N]> for Digest<F, N> {
+    fn from(elements: [F; N]) -> Self {
+        Self(elements)
+    }
+}
+
+impl<F, const N: usize> AsRef<[F]> for Digest<F, N> {
+    fn as_ref(&self) -> &[F] {
+        &self.0
+    }
+}
+
+// `serde` only implements (de)serialization for arrays of a fixed set of lengths,
+// so the const-generic width is handled manually, with the same encoding as `[F; N]`.
+impl<F: Serialize, const N: usize> Serialize for Digest<F, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for element in &self.0 {
+            tuple.serialize_element(element)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de, F: Deserialize<'de>, const N: usize> Deserialize<'de> for Digest<F, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DigestVisitor<F, const N: usize>(PhantomData<F>);
+
+        impl<'de, F: Deserialize<'de>, const N: usize> Visitor<'de> for DigestVisitor<F, N> {
+            type Value = Digest<F, N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "an array of length {N}")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut elements = Vec::with_capacity(N);
+                for i in 0..N {
+                    let element = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                    elements.push(element);
+                }
+                let Ok(elements) = elements.try_into() else {
+                    unreachable!()
+                };
+                Ok(Digest(elements))
+            }
+        }
+
+        deserializer.deserialize_tuple(N, DigestVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,228 @@
+use p3_challenger::{FieldChallenger, GrindingChallenger};
+use p3_field::{ExtensionField, Field};
+
+use crate::*;
+
+/// Dyn-compatible prover transcript, to write protocol code against
+/// `&mut dyn ProverTranscript<EF>` instead of being generic over the base field, the challenger
+/// and the const parameters of `ProverState`.
+pub trait ProverTranscript<EF: ExtensionField<PF<EF>>>: ChallengeSampler<EF> {
+    fn add_base_scalars(&mut self, scalars: &[PF<EF>]);
+
+    fn add_extension_scalars(&mut self, scalars: &[EF]);
+
+    fn observe_public_base(&mut self, scalars: &[PF<EF>]);
+
+    fn observe_public_extension(&mut self, scalars: &[EF]);
+
+    fn hint_base_scalars(&mut self, scalars: &[PF<EF>]);
+
+    fn hint_extension_scalars(&mut self, scalars: &[EF]);
+
+    fn pow_grinding(&mut self, bits: usize);
+
+    fn set_label(&mut self, label: &str);
+
+    fn clear_label(&mut self);
+
+    fn proof_size(&self) -> usize;
+}
+
+/// Dyn-compatible verifier transcript, mirroring [`ProverTranscript`].
+pub trait VerifierTranscript<EF: ExtensionField<PF<EF>>>: ChallengeSampler<EF> {
+    /// # Errors
+    /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
+    fn next_base_scalars_vec(&mut self, n: usize) -> ProofResult<Vec<PF<EF>>>;
+
+    /// # Errors
+    /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
+    fn next_extension_scalars_vec(&mut self, n: usize) -> ProofResult<Vec<EF>>;
+
+    fn observe_public_base(&mut self, scalars: &[PF<EF>]);
+
+    fn observe_public_extension(&mut self, scalars: &[EF]);
+
+    /// # Errors
+    /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
+    fn receive_hint_base_scalars(&mut self, n: usize) -> ProofResult<Vec<PF<EF>>>;
+
+    /// # Errors
+    /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
+    fn receive_hint_extension_scalars(&mut self, n: usize) -> ProofResult<Vec<EF>>;
+
+    /// # Errors
+    /// Returns `ProofError::InvalidGrindingWitness` if the witness is invalid.
+    fn check_pow_grinding(&mut self, bits: usize) -> ProofResult<()>;
+
+    fn set_label(&mut self, label: &str);
+
+    fn clear_label(&mut self);
+}
+
+impl<EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize> ProverTranscript<EF>
+    for ProverState<PF<EF>, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
+where
+    EF: ExtensionField<PF<EF>>,
+    PF<EF>: Field,
+    Challenger: FieldChallenger<PF<EF>> + GrindingChallenger<Witness = PF<EF>> + ChallengerState,
+{
+    fn add_base_scalars(&mut self, scalars: &[PF<EF>]) {
+        self.add_base_scalars(scalars);
+    }
+
+    fn add_extension_scalars(&mut self, scalars: &[EF]) {
+        self.add_extension_scalars(scalars);
+    }
+
+    fn observe_public_base(&mut self, scalars: &[PF<EF>]) {
+        self.observe_public_base(scalars);
+    }
+
+    fn observe_public_extension(&mut self, scalars: &[EF]) {
+        self.observe_public_extension(scalars);
+    }
+
+    fn hint_base_scalars(&mut self, scalars: &[PF<EF>]) {
+        self.hint_base_scalars(scalars);
+    }
+
+    fn hint_extension_scalars(&mut self, scalars: &[EF]) {
+        self.hint_extension_scalars(scalars);
+    }
+
+    fn pow_grinding(&mut self, bits: usize) {
+        self.pow_grinding(bits);
+    }
+
+    fn set_label(&mut self, label: &str) {
+        self.set_label(label);
+    }
+
+    fn clear_label(&mut self) {
+        self.clear_label();
+    }
+
+    fn proof_size(&self) -> usize {
+        self.proof_size()
+    }
+}
+
+impl<EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize> VerifierTranscript<EF>
+    for VerifierState<PF<EF>, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
+where
+    EF: ExtensionField<PF<EF>>,
+    PF<EF>: Field,
+    Challenger: FieldChallenger<PF<EF>> + GrindingChallenger<Witness = PF<EF>> + ChallengerState,
+{
+    fn next_base_scalars_vec(&mut self, n: usize) -> ProofResult<Vec<PF<EF>>> {
+        self.next_base_scalars_vec(n)
+    }
+
+    fn next_extension_scalars_vec(&mut self, n: usize) -> ProofResult<Vec<EF>> {
+        self.next_extension_scalars_vec(n)
+    }
+
+    fn observe_public_base(&mut self, scalars: &[PF<EF>]) {
+        self.observe_public_base(scalars);
+    }
+
+    fn observe_public_extension(&mut self, scalars: &[EF]) {
+        self.observe_public_extension(scalars);
+    }
+
+    fn receive_hint_base_scalars(&mut self, n: usize) -> ProofResult<Vec<PF<EF>>> {
+        self.receive_hint_base_scalars(n)
+    }
+
+    fn receive_hint_extension_scalars(&mut self, n: usize) -> ProofResult<Vec<EF>> {
+        self.receive_hint_extension_scalars(n)
+    }
+
+    fn check_pow_grinding(&mut self, bits: usize) -> ProofResult<()> {
+        self.check_pow_grinding(bits)
+    }
+
+    fn set_label(&mut self, label: &str) {
+        self.set_label(label);
+    }
+
+    fn clear_label(&mut self) {
+        self.clear_label();
+    }
+}
+
+// Shared protocol skeletons written against `Transcript` also accept the trait objects.
+
+impl<EF: ExtensionField<PF<EF>>> Transcript<EF> for dyn ProverTranscript<EF> + '_ {
+    const IS_PROVER: bool = true;
+
+    fn send_or_receive_base(
+        &mut self,
+        n: usize,
+        scalars: impl FnOnce() -> Vec<PF<EF>>,
+    ) -> ProofResult<Vec<PF<EF>>> {
+        let scalars = scalars();
+        assert_eq!(scalars.len(), n);
+        self.add_base_scalars(&scalars);
+        Ok(scalars)
+    }
+
+    fn send_or_receive_extension(
+        &mut self,
+        n: usize,
+        scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>> {
+        let scalars = scalars();
+        assert_eq!(scalars.len(), n);
+        self.add_extension_scalars(&scalars);
+        Ok(scalars)
+    }
+
+    fn hint_or_receive(
+        &mut self,
+        n: usize,
+        scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>> {
+        let scalars = scalars();
+        assert_eq!(scalars.len(), n);
+        self.hint_extension_scalars(&scalars);
+        Ok(scalars)
+    }
+
+    fn grind_or_check(&mut self, bits: usize) -> ProofResult<()> {
+        self.pow_grinding(bits);
+        Ok(())
+    }
+}
+
+impl<EF: ExtensionField<PF<EF>>> Transcript<EF> for dyn VerifierTranscript<EF> + '_ {
+    const IS_PROVER: bool = false;
+
+    fn send_or_receive_base(
+        &mut self,
+        n: usize,
+        _scalars: impl FnOnce() -> Vec<PF<EF>>,
+    ) -> ProofResult<Vec<PF<EF>>> {
+        self.next_base_scalars_vec(n)
+    }
+
+    fn send_or_receive_extension(
+        &mut self,
+        n: usize,
+        _scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>> {
+        self.next_extension_scalars_vec(n)
+    }
+
+    fn hint_or_receive(
+        &mut self,
+        n: usize,
+        _scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>> {
+        self.receive_hint_extension_scalars(n)
+    }
+
+    fn grind_or_check(&mut self, bits: usize) -> ProofResult<()> {
+        self.check_pow_grinding(bits)
+    }
+}
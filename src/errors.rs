@@ -29,6 +29,14 @@ pub enum ProofError {
     ExceededTranscript,
     /// Invalid Pow Grinding
     InvalidGrindingWitness,
+    /// The proof exceeds the verifier's resource limits.
+    LimitExceeded,
+    /// The proof was padded to a different vector length than the verifier expects.
+    PaddingMismatch,
+    /// The proof was made for a different protocol or parameter set than the verifier's.
+    ProtocolMismatch,
+    /// The proof configuration header is not supported by the verifier.
+    UnsupportedConfig,
 }
 
 /// The result type when trying to prove or verify a proof using Fiat-Shamir.
@@ -40,6 +48,10 @@ impl Display for ProofError {
             Self::InvalidProof => write!(f, "Invalid proof"),
             Self::ExceededTranscript => write!(f, "Verifier exceeded transcript length"),
             Self::InvalidGrindingWitness => write!(f, "Invalid grinding witness"),
+            Self::LimitExceeded => write!(f, "Proof exceeds verifier resource limits"),
+            Self::PaddingMismatch => write!(f, "Proof padding vector length mismatch"),
+            Self::ProtocolMismatch => write!(f, "Proof protocol or parameters mismatch"),
+            Self::UnsupportedConfig => write!(f, "Unsupported proof configuration"),
         }
     }
 }
@@ -0,0 +1,296 @@
+use std::any::TypeId;
+
+use p3_challenger::{CanObserve, CanSample, CanSampleBits, FieldChallenger, GrindingChallenger};
+use p3_field::{ExtensionField, PrimeField32};
+use p3_keccak::Keccak256Hash;
+use p3_koala_bear::KoalaBear;
+use p3_symmetric::CryptographicHasher;
+
+use crate::*;
+
+/// Signature of the verifier entry point targeted by [`encode_calldata`].
+pub const EVM_VERIFY_SIGNATURE: &str = "verify(uint256[],uint256[],uint256[][])";
+
+/// Encode a field element as a big-endian 32-byte EVM word.
+pub fn evm_word<F: PrimeField32>(x: F) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[28..].copy_from_slice(&x.as_canonical_u32().to_be_bytes());
+    word
+}
+
+fn usize_word(n: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(n as u64).to_be_bytes());
+    word
+}
+
+fn keccak(bytes: &[u8]) -> [u8; 32] {
+    Keccak256Hash.hash_iter(bytes.iter().copied())
+}
+
+/// Reduce a big-endian 256-bit word modulo `modulus` (the `%` of the EVM).
+fn reduce_word(word: &[u8; 32], modulus: u32) -> u32 {
+    let modulus = u64::from(modulus);
+    word.iter()
+        .fold(0u64, |acc, &byte| ((acc << 8) | u64::from(byte)) % modulus) as u32
+}
+
+/// Low `bits` bits of a big-endian 256-bit word.
+fn low_bits(word: &[u8; 32], bits: usize) -> usize {
+    assert!(bits < usize::BITS as usize);
+    let low = u64::from_be_bytes(word[24..].try_into().unwrap()) as usize;
+    low & ((1 << bits) - 1)
+}
+
+/// Challenger matching the transcript of a Solidity verifier.
+///
+/// The transcript holds a 32-byte `state` and a squeeze `counter`:
+/// - observed elements are buffered as big-endian 32-byte words;
+/// - before squeezing, buffered words are absorbed with
+///   `state = keccak256(abi.encodePacked(state, words))` and `counter` is reset;
+/// - each squeeze returns `keccak256(abi.encodePacked(state, counter++))` as a `uint256`,
+///   reduced modulo the field order for a field challenge, or masked to its low bits.
+///
+/// Since observed elements are encoded exactly as in [`encode_calldata`], an on-chain verifier
+/// can absorb a message by hashing the corresponding slice of calldata.
+#[derive(Clone, Debug)]
+pub struct EvmChallenger<F> {
+    state: [u8; 32],
+    pending: Vec<F>,
+    counter: usize,
+}
+
+pub type KoalaBearEvmChallenger = EvmChallenger<KoalaBear>;
+
+/// EVM transcript profile: no padding (it only costs calldata), Keccak-256 challenger.
+///
+/// Use `ProverState::new_evm` / `VerifierState::new_evm`, which enforce the absence of padding.
+pub type EvmProver<EF, const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN> =
+    ProverState<PF<EF>, EF, EvmChallenger<PF<EF>>, DIGEST_LEN>;
+pub type EvmVerifier<EF, const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN> =
+    VerifierState<PF<EF>, EF, EvmChallenger<PF<EF>>, DIGEST_LEN>;
+
+impl<F: PrimeField32> EvmChallenger<F> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: [0; 32],
+            pending: Vec::new(),
+            counter: 0,
+        }
+    }
+
+    fn squeeze(&mut self) -> [u8; 32] {
+        if !self.pending.is_empty() {
+            let mut bytes = self.state.to_vec();
+            bytes.extend(self.pending.drain(..).flat_map(evm_word));
+            self.state = keccak(&bytes);
+            self.counter = 0;
+        }
+        let mut bytes = self.state.to_vec();
+        bytes.extend(usize_word(self.counter));
+        self.counter += 1;
+        keccak(&bytes)
+    }
+}
+
+impl<F: PrimeField32, EF: ExtensionField<F>, const DIGEST_LEN: usize, const VECTOR_LEN: usize>
+    ProverState<F, EF, EvmChallenger<F>, DIGEST_LEN, VECTOR_LEN>
+{
+    /// Create a prover following the EVM transcript profile: fresh [`EvmChallenger`], no padding.
+    #[must_use]
+    pub fn new_evm() -> Self {
+        Self::new(EvmChallenger::new(), false)
+    }
+}
+
+impl<F: PrimeField32, EF: ExtensionField<F>, const DIGEST_LEN: usize, const VECTOR_LEN: usize>
+    VerifierState<F, EF, EvmChallenger<F>, DIGEST_LEN, VECTOR_LEN>
+{
+    /// Create a verifier following the EVM transcript profile: fresh [`EvmChallenger`], no
+    /// padding.
+    ///
+    /// # Errors
+    /// Returns `ProofError::PaddingMismatch` if the proof is padded.
+    pub fn new_evm(proof: Proof<F, DIGEST_LEN>) -> ProofResult<Self> {
+        if proof.padding {
+            return Err(ProofError::PaddingMismatch);
+        }
+        Self::try_new(proof, EvmChallenger::new())
+    }
+}
+
+impl<F: PrimeField32> Default for EvmChallenger<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField32> CanObserve<F> for EvmChallenger<F> {
+    fn observe(&mut self, value: F) {
+        self.pending.push(value);
+    }
+}
+
+impl<F: PrimeField32> CanSample<F> for EvmChallenger<F> {
+    fn sample(&mut self) -> F {
+        F::from_u32(reduce_word(&self.squeeze(), F::ORDER_U32))
+    }
+}
+
+impl<F: PrimeField32> CanSampleBits<usize> for EvmChallenger<F> {
+    fn sample_bits(&mut self, bits: usize) -> usize {
+        low_bits(&self.squeeze(), bits)
+    }
+}
+
+impl<F: PrimeField32> FieldChallenger<F> for EvmChallenger<F> {}
+
+impl<F: PrimeField32> GrindingChallenger for EvmChallenger<F> {
+    type Witness = F;
+
+    fn grind(&mut self, bits: usize) -> F {
+        let witness = (0..F::ORDER_U32)
+            .map(F::from_u32)
+            .find(|&witness| self.clone().check_witness(bits, witness))
+            .expect("failed to find a grinding witness");
+        assert!(self.check_witness(bits, witness));
+        witness
+    }
+
+    fn check_witness(&mut self, bits: usize, witness: F) -> bool {
+        self.observe(witness);
+        self.sample_bits(bits) == 0
+    }
+}
+
+impl<F: PrimeField32> ChallengerState for EvmChallenger<F> {
+    fn state(&self) -> String {
+        format!(
+            "state: {:?}, counter: {}, pending: {:?}",
+            self.state, self.counter, self.pending
+        )
+    }
+
+    fn kind(&self) -> Option<ChallengerKind> {
+        (TypeId::of::<F>() == TypeId::of::<KoalaBear>()).then_some(ChallengerKind::EvmKoalaBear)
+    }
+}
+
+/// A call to the on-chain transcript, for [`evm_reference_transcript`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EvmTranscriptCall {
+    /// Absorb 32-byte words.
+    Absorb(Vec<[u8; 32]>),
+    /// Squeeze a challenge reduced modulo the field order.
+    Squeeze,
+    /// Squeeze a challenge of `bits` bits.
+    SqueezeBits(usize),
+}
+
+/// A `uint256`, as four 64-bit limbs (most significant first).
+type U256 = [u64; 4];
+
+fn u256_from_be_bytes(bytes: &[u8; 32]) -> U256 {
+    std::array::from_fn(|i| u64::from_be_bytes(bytes[8 * i..8 * (i + 1)].try_into().unwrap()))
+}
+
+fn u256_to_be_bytes(x: U256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(x) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+}
+
+/// Pure-Rust reference of the challenge derivation of a Solidity verifier, word by word,
+/// for differential testing against [`EvmChallenger`].
+///
+/// Only the Keccak-256 primitive is shared with [`EvmChallenger`]: words are handled as
+/// `uint256` limbs, as the EVM does.
+///
+/// # Arguments
+/// - `modulus`: Order of the base field.
+/// - `calls`: Transcript calls, in order.
+///
+/// # Returns
+/// The challenges returned by the squeezes, in order.
+pub fn evm_reference_transcript(modulus: u32, calls: &[EvmTranscriptCall]) -> Vec<u64> {
+    let mut state = [0u8; 32];
+    let mut counter = 0u64;
+    let mut calldata = Vec::new();
+    let mut challenges = Vec::new();
+    for call in calls {
+        let bits = match call {
+            EvmTranscriptCall::Absorb(words) => {
+                calldata.extend(words.iter().flatten());
+                continue;
+            }
+            EvmTranscriptCall::Squeeze => None,
+            EvmTranscriptCall::SqueezeBits(bits) => Some(*bits),
+        };
+        if !calldata.is_empty() {
+            // state = keccak256(abi.encodePacked(state, calldata[start:end]))
+            state = keccak(&[state.as_slice(), &calldata].concat());
+            calldata.clear();
+            counter = 0;
+        }
+        // r = uint256(keccak256(abi.encodePacked(state, counter++)))
+        let r = u256_from_be_bytes(&keccak(
+            &[state, u256_to_be_bytes([0, 0, 0, counter])].concat(),
+        ));
+        counter += 1;
+        challenges.push(match bits {
+            // r % modulus
+            None => r.iter().fold(0u128, |acc, &limb| {
+                ((acc << 64) | u128::from(limb)) % u128::from(modulus)
+            }) as u64,
+            // r & ((1 << bits) - 1)
+            Some(bits) => r[3] & ((1u64 << bits) - 1),
+        });
+    }
+    challenges
+}
+
+fn encode_array<F: PrimeField32>(elements: &[F]) -> Vec<u8> {
+    let mut encoded = usize_word(elements.len()).to_vec();
+    encoded.extend(elements.iter().copied().flat_map(evm_word));
+    encoded
+}
+
+/// ABI encoding of a tuple of dynamic values: offsets (relative to the start of the tuple)
+/// followed by the encoded values.
+fn encode_dynamic_tuple(values: Vec<Vec<u8>>) -> Vec<u8> {
+    let mut offset = 32 * values.len();
+    let mut encoded = Vec::new();
+    for value in &values {
+        encoded.extend(usize_word(offset));
+        offset += value.len();
+    }
+    encoded.extend(values.into_iter().flatten());
+    encoded
+}
+
+/// Encode a proof as calldata for [`EVM_VERIFY_SIGNATURE`]: the proof data, the advice
+/// scalars, and the merkle paths (one flattened array of digest elements per path), each
+/// element as a `uint256`.
+pub fn encode_calldata<F: PrimeField32, const DIGEST_LEN: usize>(
+    proof: &Proof<F, DIGEST_LEN>,
+) -> Vec<u8> {
+    let merkle_paths = proof
+        .advice
+        .merkle_paths
+        .iter()
+        .map(|path| encode_array(&path.iter().flat_map(|node| node.0).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    let mut merkle_paths_encoding = usize_word(merkle_paths.len()).to_vec();
+    merkle_paths_encoding.extend(encode_dynamic_tuple(merkle_paths));
+
+    let mut calldata = keccak(EVM_VERIFY_SIGNATURE.as_bytes())[..4].to_vec();
+    calldata.extend(encode_dynamic_tuple(vec![
+        encode_array(&proof.proof_data),
+        encode_array(&proof.advice.scalars),
+        merkle_paths_encoding,
+    ]));
+    calldata
+}
@@ -0,0 +1,64 @@
+use std::{any::TypeId, fmt::Debug};
+
+use p3_blake3::Blake3;
+use p3_challenger::{HashChallenger, SerializingChallenger32};
+use p3_field::PrimeField32;
+use p3_keccak::Keccak256Hash;
+use p3_koala_bear::KoalaBear;
+use p3_sha256::Sha256;
+use p3_symmetric::CryptographicHasher;
+
+use crate::*;
+
+/// Challengers over byte-oriented hashes, for proofs checked by external (non-recursive)
+/// verifiers, which shouldn't need a Poseidon2 implementation.
+///
+/// Field elements are serialized to bytes before being absorbed, and challenges are sampled
+/// from the hash output, as in Plonky3's `SerializingChallenger32`.
+pub type ByteHashChallenger<F, H> = SerializingChallenger32<F, HashChallenger<u8, H, 32>>;
+
+pub type KoalaBearKeccakChallenger = ByteHashChallenger<KoalaBear, Keccak256Hash>;
+pub type KoalaBearSha256Challenger = ByteHashChallenger<KoalaBear, Sha256>;
+pub type KoalaBearBlake3Challenger = ByteHashChallenger<KoalaBear, Blake3>;
+
+/// A KoalaBear challenger over Keccak-256.
+#[must_use]
+pub fn keccak_challenger() -> KoalaBearKeccakChallenger {
+    SerializingChallenger32::from_hasher(Vec::new(), Keccak256Hash)
+}
+
+/// A KoalaBear challenger over SHA-256.
+#[must_use]
+pub fn sha256_challenger() -> KoalaBearSha256Challenger {
+    SerializingChallenger32::from_hasher(Vec::new(), Sha256)
+}
+
+/// A KoalaBear challenger over Blake3.
+#[must_use]
+pub fn blake3_challenger() -> KoalaBearBlake3Challenger {
+    SerializingChallenger32::from_hasher(Vec::new(), Blake3)
+}
+
+impl<F, H> ChallengerState for ByteHashChallenger<F, H>
+where
+    F: PrimeField32,
+    H: CryptographicHasher<u8, [u8; 32]> + 'static,
+    Self: Debug,
+{
+    fn state(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn kind(&self) -> Option<ChallengerKind> {
+        let id = TypeId::of::<Self>();
+        if id == TypeId::of::<KoalaBearKeccakChallenger>() {
+            Some(ChallengerKind::KeccakKoalaBear)
+        } else if id == TypeId::of::<KoalaBearSha256Challenger>() {
+            Some(ChallengerKind::Sha256KoalaBear)
+        } else if id == TypeId::of::<KoalaBearBlake3Challenger>() {
+            Some(ChallengerKind::Blake3KoalaBear)
+        } else {
+            None
+        }
+    }
+}
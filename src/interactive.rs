@@ -0,0 +1,442 @@
+use std::{
+    collections::VecDeque,
+    io::{Read, Write},
+    marker::PhantomData,
+    net::TcpStream,
+    sync::{
+        Arc, Mutex,
+        mpsc::{Receiver, Sender, channel},
+    },
+    thread,
+};
+
+use p3_challenger::{CanObserve, CanSample, CanSampleBits, FieldChallenger, GrindingChallenger};
+use p3_field::{ExtensionField, Field, PrimeField32};
+use rand::{
+    Rng,
+    distr::{Distribution, StandardUniform},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Message exchanged between the prover and the verifier in interactive mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InteractiveMessage<F> {
+    /// A base field element sent by the prover.
+    Observe(F),
+    /// The prover requests a base field challenge.
+    SampleRequest,
+    /// The prover requests a challenge of the given number of bits.
+    SampleBitsRequest(usize),
+    /// A base field challenge sent by the verifier.
+    Challenge(F),
+    /// A `bits`-bit challenge sent by the verifier.
+    ChallengeBits(usize),
+}
+
+/// A bidirectional channel between the prover and the verifier.
+pub trait Transport<F>: Send {
+    fn send(&mut self, message: InteractiveMessage<F>);
+
+    /// Receive the next message, or `None` once the other party has disconnected or sent a
+    /// malformed message.
+    fn recv(&mut self) -> Option<InteractiveMessage<F>>;
+}
+
+/// In-process transport, built on `std::sync::mpsc` channels.
+#[derive(Debug)]
+pub struct InProcessTransport<F> {
+    sender: Sender<InteractiveMessage<F>>,
+    receiver: Receiver<InteractiveMessage<F>>,
+}
+
+/// Create the two connected ends of an in-process transport.
+pub fn in_process_transports<F>() -> (InProcessTransport<F>, InProcessTransport<F>) {
+    let (prover_sender, verifier_receiver) = channel();
+    let (verifier_sender, prover_receiver) = channel();
+    (
+        InProcessTransport {
+            sender: prover_sender,
+            receiver: prover_receiver,
+        },
+        InProcessTransport {
+            sender: verifier_sender,
+            receiver: verifier_receiver,
+        },
+    )
+}
+
+impl<F: Send> Transport<F> for InProcessTransport<F> {
+    fn send(&mut self, message: InteractiveMessage<F>) {
+        self.sender
+            .send(message)
+            .expect("interactive transport disconnected");
+    }
+
+    fn recv(&mut self) -> Option<InteractiveMessage<F>> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// Transport over a socket (e.g. to a verifier on `localhost`).
+///
+/// Each message is encoded as a tag byte followed by an 8-byte little-endian payload
+/// (field elements are sent in canonical form).
+#[derive(Debug)]
+pub struct SocketTransport<F> {
+    stream: TcpStream,
+    _field: PhantomData<F>,
+}
+
+impl<F> SocketTransport<F> {
+    #[must_use]
+    pub const fn new(stream: TcpStream) -> Self {
+        Self {
+            stream,
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField32> Transport<F> for SocketTransport<F> {
+    fn send(&mut self, message: InteractiveMessage<F>) {
+        let (tag, payload) = match message {
+            InteractiveMessage::Observe(x) => (0u8, u64::from(x.as_canonical_u32())),
+            InteractiveMessage::SampleRequest => (1, 0),
+            InteractiveMessage::SampleBitsRequest(bits) => (2, bits as u64),
+            InteractiveMessage::Challenge(x) => (3, u64::from(x.as_canonical_u32())),
+            InteractiveMessage::ChallengeBits(value) => (4, value as u64),
+        };
+        let mut bytes = [0u8; 9];
+        bytes[0] = tag;
+        bytes[1..].copy_from_slice(&payload.to_le_bytes());
+        self.stream
+            .write_all(&bytes)
+            .expect("interactive transport disconnected");
+    }
+
+    fn recv(&mut self) -> Option<InteractiveMessage<F>> {
+        let mut bytes = [0u8; 9];
+        self.stream.read_exact(&mut bytes).ok()?;
+        let payload = u64::from_le_bytes(bytes[1..].try_into().unwrap());
+        let field_element = || {
+            u32::try_from(payload)
+                .ok()
+                .filter(|&value| value < F::ORDER_U32)
+                .map(F::from_u32)
+        };
+        Some(match bytes[0] {
+            0 => InteractiveMessage::Observe(field_element()?),
+            1 => InteractiveMessage::SampleRequest,
+            2 => InteractiveMessage::SampleBitsRequest(usize::try_from(payload).ok()?),
+            3 => InteractiveMessage::Challenge(field_element()?),
+            4 => InteractiveMessage::ChallengeBits(usize::try_from(payload).ok()?),
+            _ => return None,
+        })
+    }
+}
+
+/// Prover-side challenger of the interactive mode: observed elements are sent to the verifier,
+/// and challenges are received from it.
+///
+/// There is no proof of work in interactive mode: grinding produces a zero witness, which is
+/// sent to the verifier like any other message. Clones share the same connection.
+#[derive(Debug)]
+pub struct InteractiveProverChallenger<F, T> {
+    transport: Arc<Mutex<T>>,
+    n_messages: usize,
+    _field: PhantomData<F>,
+}
+
+impl<F, T> Clone for InteractiveProverChallenger<F, T> {
+    fn clone(&self) -> Self {
+        Self {
+            transport: self.transport.clone(),
+            n_messages: self.n_messages,
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F, T: Transport<F>> InteractiveProverChallenger<F, T> {
+    #[must_use]
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport: Arc::new(Mutex::new(transport)),
+            n_messages: 0,
+            _field: PhantomData,
+        }
+    }
+
+    fn request(&mut self, message: InteractiveMessage<F>) -> InteractiveMessage<F> {
+        self.n_messages += 2;
+        let mut transport = self.transport.lock().unwrap();
+        transport.send(message);
+        transport
+            .recv()
+            .expect("verifier disconnected before sending a challenge")
+    }
+}
+
+impl<F: Field, T: Transport<F>> CanObserve<F> for InteractiveProverChallenger<F, T> {
+    fn observe(&mut self, value: F) {
+        self.n_messages += 1;
+        self.transport
+            .lock()
+            .unwrap()
+            .send(InteractiveMessage::Observe(value));
+    }
+}
+
+impl<F: Field, T: Transport<F>> CanSample<F> for InteractiveProverChallenger<F, T> {
+    fn sample(&mut self) -> F {
+        match self.request(InteractiveMessage::SampleRequest) {
+            InteractiveMessage::Challenge(challenge) => challenge,
+            message => panic!("expected a challenge, received {message:?}"),
+        }
+    }
+}
+
+impl<F: Field, T: Transport<F>> CanSampleBits<usize> for InteractiveProverChallenger<F, T> {
+    fn sample_bits(&mut self, bits: usize) -> usize {
+        match self.request(InteractiveMessage::SampleBitsRequest(bits)) {
+            InteractiveMessage::ChallengeBits(value) => value,
+            message => panic!("expected a challenge, received {message:?}"),
+        }
+    }
+}
+
+impl<F: Field, T: Transport<F>> FieldChallenger<F> for InteractiveProverChallenger<F, T> {}
+
+impl<F: Field, T: Transport<F>> GrindingChallenger for InteractiveProverChallenger<F, T> {
+    type Witness = F;
+
+    fn grind(&mut self, bits: usize) -> F {
+        assert!(self.check_witness(bits, F::ZERO));
+        F::ZERO
+    }
+
+    fn check_witness(&mut self, _bits: usize, witness: F) -> bool {
+        self.observe(witness);
+        true
+    }
+}
+
+impl<F, T> ChallengerState for InteractiveProverChallenger<F, T> {
+    fn state(&self) -> String {
+        format!("interactive prover, {} messages", self.n_messages)
+    }
+}
+
+/// Messages sent by the prover and challenges sent by the verifier, in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InteractiveTranscript<F> {
+    pub messages: Vec<InteractiveMessage<F>>,
+}
+
+/// Verifier party of the interactive mode: answers each challenge request of the prover with
+/// fresh randomness, and records the transcript.
+///
+/// Since the protocols are public-coin, the verifier's decision only depends on the transcript,
+/// and is taken once the prover is done, by running the verifier code on a [`ReplayChallenger`].
+#[derive(Debug)]
+pub struct InteractiveVerifier<F, T, R> {
+    transport: T,
+    rng: R,
+    transcript: InteractiveTranscript<F>,
+}
+
+impl<F, T, R> InteractiveVerifier<F, T, R>
+where
+    F: Field,
+    T: Transport<F>,
+    R: Rng,
+    StandardUniform: Distribution<F>,
+{
+    #[must_use]
+    pub fn new(transport: T, rng: R) -> Self {
+        Self {
+            transport,
+            rng,
+            transcript: InteractiveTranscript::default(),
+        }
+    }
+
+    /// Answer the prover until it disconnects, and return the transcript.
+    ///
+    /// # Errors
+    /// Returns `ProofError::InvalidProof` if the prover sends a verifier message or requests
+    /// more bits than a `usize` holds. The session is then closed.
+    pub fn run(mut self) -> ProofResult<InteractiveTranscript<F>> {
+        while let Some(message) = self.transport.recv() {
+            let response = match message {
+                InteractiveMessage::Observe(_) => {
+                    self.transcript.messages.push(message);
+                    continue;
+                }
+                InteractiveMessage::SampleRequest => {
+                    InteractiveMessage::Challenge(self.rng.random())
+                }
+                InteractiveMessage::SampleBitsRequest(bits) => {
+                    if bits >= usize::BITS as usize {
+                        return Err(ProofError::InvalidProof);
+                    }
+                    let value = self.rng.random::<u64>() as usize & ((1 << bits) - 1);
+                    InteractiveMessage::ChallengeBits(value)
+                }
+                InteractiveMessage::Challenge(_) | InteractiveMessage::ChallengeBits(_) => {
+                    return Err(ProofError::InvalidProof);
+                }
+            };
+            self.transport.send(response);
+            self.transcript.messages.push(response);
+        }
+        Ok(self.transcript)
+    }
+}
+
+/// Verifier-side challenger of the interactive mode, replaying the challenges of an
+/// [`InteractiveTranscript`].
+///
+/// Each observed element must match the next message of the prover in the transcript, and each
+/// sample returns the next challenge. Any deviation marks the transcript as inconsistent
+/// (see `is_consistent`), in which case the proof must be rejected.
+#[derive(Clone, Debug)]
+pub struct ReplayChallenger<F> {
+    messages: VecDeque<InteractiveMessage<F>>,
+    consistent: bool,
+}
+
+impl<F> ReplayChallenger<F> {
+    #[must_use]
+    pub fn new(transcript: InteractiveTranscript<F>) -> Self {
+        Self {
+            messages: transcript.messages.into(),
+            consistent: true,
+        }
+    }
+
+    /// Whether the verifier followed the transcript exactly, up to its end.
+    pub fn is_consistent(&self) -> bool {
+        self.consistent && self.messages.is_empty()
+    }
+}
+
+impl<F: Field> CanObserve<F> for ReplayChallenger<F> {
+    fn observe(&mut self, value: F) {
+        if self.messages.pop_front() != Some(InteractiveMessage::Observe(value)) {
+            self.consistent = false;
+        }
+    }
+}
+
+impl<F: Field> CanSample<F> for ReplayChallenger<F> {
+    fn sample(&mut self) -> F {
+        match self.messages.pop_front() {
+            Some(InteractiveMessage::Challenge(challenge)) => challenge,
+            _ => {
+                self.consistent = false;
+                F::ZERO
+            }
+        }
+    }
+}
+
+impl<F: Field> CanSampleBits<usize> for ReplayChallenger<F> {
+    fn sample_bits(&mut self, _bits: usize) -> usize {
+        match self.messages.pop_front() {
+            Some(InteractiveMessage::ChallengeBits(value)) => value,
+            _ => {
+                self.consistent = false;
+                0
+            }
+        }
+    }
+}
+
+impl<F: Field> FieldChallenger<F> for ReplayChallenger<F> {}
+
+impl<F: Field> GrindingChallenger for ReplayChallenger<F> {
+    type Witness = F;
+
+    /// Replay the grinding witness sent by the prover, i.e. the next observed element.
+    fn grind(&mut self, _bits: usize) -> F {
+        match self.messages.pop_front() {
+            Some(InteractiveMessage::Observe(witness)) => witness,
+            _ => {
+                self.consistent = false;
+                F::ZERO
+            }
+        }
+    }
+
+    fn check_witness(&mut self, _bits: usize, witness: F) -> bool {
+        self.observe(witness);
+        self.consistent
+    }
+}
+
+impl<F> ChallengerState for ReplayChallenger<F> {
+    fn state(&self) -> String {
+        format!("replay, {} messages left", self.messages.len())
+    }
+}
+
+/// Run a protocol interactively: the prover runs in its own thread, connected by an in-process
+/// channel to a verifier sending fresh random challenges drawn from `rng`. The verifier code
+/// then checks the resulting proof against the recorded challenges.
+///
+/// # Errors
+/// Returns the error of `verify`, or `ProofError::InvalidProof` if the prover sent a malformed
+/// message or the proof does not match the messages sent by the prover during the interaction.
+pub fn run_interactive<F, EF, R, P, V, const DIGEST_LEN: usize, const VECTOR_LEN: usize>(
+    rng: R,
+    padding: bool,
+    prove: P,
+    verify: V,
+) -> ProofResult<()>
+where
+    F: Field,
+    EF: ExtensionField<F>,
+    R: Rng,
+    StandardUniform: Distribution<F>,
+    P: FnOnce(
+            &mut ProverState<
+                F,
+                EF,
+                InteractiveProverChallenger<F, InProcessTransport<F>>,
+                DIGEST_LEN,
+                VECTOR_LEN,
+            >,
+        ) + Send,
+    V: FnOnce(
+        &mut VerifierState<F, EF, ReplayChallenger<F>, DIGEST_LEN, VECTOR_LEN>,
+    ) -> ProofResult<()>,
+{
+    let (prover_transport, verifier_transport) = in_process_transports();
+
+    let (proof, transcript) = thread::scope(|scope| {
+        let prover = scope.spawn(move || {
+            let mut prover =
+                ProverState::new(InteractiveProverChallenger::new(prover_transport), padding);
+            prove(&mut prover);
+            prover.into_proof()
+        });
+        let transcript = InteractiveVerifier::new(verifier_transport, rng).run();
+        // If the verifier ended the session, the prover panics on the closed transport: report
+        // the verifier's error instead.
+        match (prover.join(), transcript) {
+            (_, Err(err)) => Err(err),
+            (Ok(proof), Ok(transcript)) => Ok((proof, transcript)),
+            (Err(panic), Ok(_)) => std::panic::resume_unwind(panic),
+        }
+    })?;
+
+    let mut verifier = VerifierState::try_new(proof, ReplayChallenger::new(transcript))?;
+    verify(&mut verifier)?;
+    if !verifier.challenger().is_consistent() {
+        return Err(ProofError::InvalidProof);
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+/// A single step of the verifier's transcript schedule.
+///
+/// Offsets are indices into `proof_data`, except for hints whose offsets are indices into the
+/// advice scalars. `padded_lanes` is the number of zero lanes following each item (non-zero only
+/// in padding mode).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TranscriptOp {
+    /// `count` items of `item_len` elements read from the proof and observed.
+    Observe {
+        offset: usize,
+        count: usize,
+        item_len: usize,
+        padded_lanes: usize,
+    },
+    /// `count` public items of `item_len` elements observed, without reading from the proof.
+    ObservePublic {
+        count: usize,
+        item_len: usize,
+        padded_lanes: usize,
+    },
+    /// `len` elements read from the advice scalars (not observed).
+    Hint { offset: usize, len: usize },
+    /// A merkle path of `depth` nodes of `digest_len` elements, read from the advice.
+    MerklePath { depth: usize, digest_len: usize },
+    /// The `len` bound hints received since the previous challenge observed, prefixed by `len`.
+    BindHints { len: usize },
+    /// `count` extension field challenges of `item_len` base elements sampled.
+    Sample { count: usize, item_len: usize },
+    /// A `bits`-bit integer sampled.
+    SampleBits { bits: usize },
+    /// A grinding witness read from the proof and checked against `bits` bits of difficulty.
+    Grind {
+        offset: usize,
+        bits: usize,
+        padded_lanes: usize,
+    },
+}
+
+/// The transcript schedule followed by a verifier, as recorded by
+/// `VerifierState::record_layout`.
+///
+/// It describes, independently of the proof values, where each piece of data lives in the proof
+/// and in which order the challenger is used, so that a recursive verifier (in the lean ISA) can
+/// be generated from it or checked against it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TranscriptLayout {
+    /// Whether the proof uses padding by `vector_len`.
+    pub padding: bool,
+    /// Vector length used for padding.
+    pub vector_len: usize,
+    /// Transcript operations, in execution order.
+    pub ops: Vec<TranscriptOp>,
+}
+
+impl TranscriptLayout {
+    #[must_use]
+    pub const fn new(padding: bool, vector_len: usize) -> Self {
+        Self {
+            padding,
+            vector_len,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Append `op`, merging consecutive samples of the same kind.
+    pub(crate) fn push(&mut self, op: TranscriptOp) {
+        if let (
+            Some(TranscriptOp::Sample { count, item_len }),
+            TranscriptOp::Sample {
+                count: new_count,
+                item_len: new_item_len,
+            },
+        ) = (self.ops.last_mut(), &op)
+            && *item_len == *new_item_len
+        {
+            *count += new_count;
+            return;
+        }
+        self.ops.push(op);
+    }
+
+    /// Number of `proof_data` elements consumed by the schedule.
+    pub fn proof_data_len(&self) -> usize {
+        self.ops
+            .iter()
+            .map(|op| match *op {
+                TranscriptOp::Observe {
+                    count,
+                    item_len,
+                    padded_lanes,
+                    ..
+                } => count * (item_len + padded_lanes),
+                TranscriptOp::Grind { padded_lanes, .. } => 1 + padded_lanes,
+                TranscriptOp::ObservePublic { .. }
+                | TranscriptOp::Hint { .. }
+                | TranscriptOp::MerklePath { .. }
+                | TranscriptOp::BindHints { .. }
+                | TranscriptOp::Sample { .. }
+                | TranscriptOp::SampleBits { .. } => 0,
+            })
+            .sum()
+    }
+
+    /// Number of advice scalars consumed by the schedule.
+    pub fn advice_len(&self) -> usize {
+        self.ops
+            .iter()
+            .map(|op| match *op {
+                TranscriptOp::Hint { len, .. } => len,
+                _ => 0,
+            })
+            .sum()
+    }
+}
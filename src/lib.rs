@@ -20,7 +20,62 @@ pub use utils::*;
 mod wrappers;
 pub use wrappers::*;
 
-const LEAN_ISA_VECTOR_LEN: usize = 8;
+mod tamper;
+pub use tamper::*;
+
+mod digest;
+pub use digest::*;
+
+mod size_report;
+pub use size_report::*;
+
+mod limits;
+pub use limits::*;
+
+mod layout;
+pub use layout::*;
+
+mod witness;
+pub use witness::*;
+
+mod counters;
+pub use counters::*;
+
+mod wire;
+pub use wire::*;
+
+mod interactive;
+pub use interactive::*;
+
+mod hash_challengers;
+pub use hash_challengers::*;
+
+mod evm;
+pub use evm::*;
+
+mod protocol;
+pub use protocol::*;
+
+mod signature;
+pub use signature::*;
+
+mod typestate;
+pub use typestate::*;
+
+mod transcript;
+pub use transcript::*;
+
+mod dyn_transcript;
+pub use dyn_transcript::*;
+
+mod config;
+pub use config::*;
+
+mod sumcheck;
+pub use sumcheck::*;
+
+/// Vector length of the lean ISA, used by default to align padded proofs for recursion.
+pub const LEAN_ISA_VECTOR_LEN: usize = 8;
 
 pub trait ChallengeSampler<F> {
     fn sample(&mut self) -> F;
@@ -42,19 +97,60 @@ impl<F: Field, C: FieldChallenger<PF<F>> + GrindingChallenger<Witness = PF<F>> +
 
 pub trait ChallengerState {
     fn state(&self) -> String;
+
+    /// Number of permutations performed so far, if the challenger keeps track of it
+    /// (e.g. when built on a `CountingPermutation`).
+    fn n_permutations(&self) -> Option<usize> {
+        None
+    }
+
+    /// The runtime-selectable configuration of this challenger, if any (recorded in the proof
+    /// header, see `ProofConfig`).
+    fn kind(&self) -> Option<ChallengerKind> {
+        None
+    }
+
+    /// Called by `VerifierState` right before it uses the challenger for `op`, performed under
+    /// `label`, so that challengers recording their permutations can attribute them.
+    fn set_transcript_op(&mut self, _op: &TranscriptOp, _label: Option<&str>) {}
 }
 
 impl ChallengerState for DuplexChallenger<KoalaBear, Poseidon2KoalaBear<16>, 16, 8> {
     fn state(&self) -> String {
         format!("{:?}", self.sponge_state)
     }
-}
 
+    fn kind(&self) -> Option<ChallengerKind> {
+        Some(ChallengerKind::Poseidon2KoalaBear)
+    }
+}
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct Proof<F> {
+pub struct Proof<F, const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN> {
+    /// The elements absorbed by the challenger (Fiat-Shamir bound), followed in padding mode by
+    /// zero lanes. The padding lanes of grinding witnesses are not absorbed: the verifier checks
+    /// that they are zero instead.
     pub proof_data: Vec<F>,
     pub padding: bool,
+    /// Vector length the proof data is aligned to when `padding` is set.
+    pub vector_len: usize,
     pub proof_size: usize,
-    pub merkle_hints: VecDeque<Vec<[F; 8]>>,
-}
\ No newline at end of file
+    /// Unobserved data (hints), not bound by Fiat-Shamir.
+    pub advice: Advice<F, DIGEST_LEN>,
+    /// Protocol and parameter set the proof was made for, if bound at transcript creation.
+    #[serde(default)]
+    pub protocol_id: Option<ProtocolId>,
+    /// Configuration the proof was made with, for runtime selection of the verifier.
+    #[serde(default)]
+    pub config: ProofConfig,
+}
+
+/// Advice section of a [`Proof`]: all the data sent by the prover that is never observed by
+/// the challenger, and that the protocol must therefore constrain by other means.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Advice<F, const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN> {
+    /// Scalars sent with `hint_base_scalars` / `hint_extension_scalars`.
+    pub scalars: Vec<F>,
+    /// Paths sent with `hint_merkle_path`.
+    pub merkle_paths: VecDeque<Vec<Digest<F, DIGEST_LEN>>>,
+}
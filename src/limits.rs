@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Resource limits enforced by the verifier before processing a proof.
+///
+/// Verifiers of untrusted proofs (e.g. received from the network) should bound the work
+/// and memory spent on a proof before doing any hashing. The default is unlimited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VerifierLimits {
+    /// Maximum number of elements in `proof_data`, also bounding the size of a single read.
+    pub max_proof_data_len: usize,
+    /// Maximum number of advice scalars.
+    pub max_advice_len: usize,
+    /// Maximum number of merkle paths in the advice.
+    pub max_merkle_hints: usize,
+    /// Maximum number of nodes in a single merkle path.
+    pub max_merkle_path_depth: usize,
+    /// Maximum total number of field elements (proof data and advice, including padding).
+    pub max_total_size: usize,
+}
+
+impl VerifierLimits {
+    pub const UNLIMITED: Self = Self {
+        max_proof_data_len: usize::MAX,
+        max_advice_len: usize::MAX,
+        max_merkle_hints: usize::MAX,
+        max_merkle_path_depth: usize::MAX,
+        max_total_size: usize::MAX,
+    };
+
+    /// Check the shape of `proof` against the limits.
+    ///
+    /// # Errors
+    /// Returns `ProofError::LimitExceeded` if any limit is exceeded.
+    pub fn check_proof<F, const DIGEST_LEN: usize>(
+        &self,
+        proof: &Proof<F, DIGEST_LEN>,
+    ) -> ProofResult<()> {
+        let merkle_paths = &proof.advice.merkle_paths;
+        if proof.proof_data.len() > self.max_proof_data_len
+            || proof.advice.scalars.len() > self.max_advice_len
+            || merkle_paths.len() > self.max_merkle_hints
+            || merkle_paths
+                .iter()
+                .any(|path| path.len() > self.max_merkle_path_depth)
+        {
+            return Err(ProofError::LimitExceeded);
+        }
+
+        let total_size = merkle_paths.iter().fold(
+            proof
+                .proof_data
+                .len()
+                .saturating_add(proof.advice.scalars.len()),
+            |acc, path| acc.saturating_add(path.len().saturating_mul(DIGEST_LEN)),
+        );
+        if total_size > self.max_total_size {
+            return Err(ProofError::LimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// Check that a single read of `n` elements stays within the limits.
+    ///
+    /// # Errors
+    /// Returns `ProofError::LimitExceeded` if `n` exceeds `max_proof_data_len`.
+    pub const fn check_read(&self, n: usize) -> ProofResult<()> {
+        if n > self.max_proof_data_len {
+            return Err(ProofError::LimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// Check that a single read of `n` advice scalars stays within the limits.
+    ///
+    /// # Errors
+    /// Returns `ProofError::LimitExceeded` if `n` exceeds `max_advice_len`.
+    pub const fn check_advice_read(&self, n: usize) -> ProofResult<()> {
+        if n > self.max_advice_len {
+            return Err(ProofError::LimitExceeded);
+        }
+        Ok(())
+    }
+}
+
+impl Default for VerifierLimits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
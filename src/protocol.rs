@@ -0,0 +1,31 @@
+use p3_keccak::Keccak256Hash;
+use p3_symmetric::CryptographicHasher;
+use serde::{Deserialize, Serialize};
+
+/// Identifier of a protocol instance: a hash of the protocol name and of its parameter set
+/// (e.g. folding factors, security bits, grinding bits).
+///
+/// It is absorbed by the challenger at transcript creation (see
+/// `ProverState::new_with_protocol`) and stored in the `Proof`, so that a verifier configured
+/// with different parameters rejects the proof upfront.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProtocolId(pub [u8; 32]);
+
+impl ProtocolId {
+    /// Compute the identifier of protocol `name` instantiated with `params`.
+    ///
+    /// The parameters are serialized to JSON, so their `Serialize` implementation must be
+    /// deterministic (e.g. no `HashMap`).
+    ///
+    /// # Panics
+    /// Panics if `params` cannot be serialized.
+    pub fn new(name: &str, params: &impl Serialize) -> Self {
+        let params = serde_json::to_vec(params).expect("failed to serialize protocol parameters");
+        let bytes = (name.len() as u64)
+            .to_le_bytes()
+            .into_iter()
+            .chain(name.bytes())
+            .chain(params);
+        Self(Keccak256Hash.hash_iter(bytes))
+    }
+}
@@ -1,7 +1,8 @@
 use crate::*;
 use p3_challenger::{FieldChallenger, GrindingChallenger};
-use p3_field::{ExtensionField, Field};
-use std::{collections::VecDeque, fmt::Debug};
+use p3_field::{BasedVectorSpace, ExtensionField, Field};
+use serde::Serialize;
+use std::fmt::Debug;
 
 /// State held by the prover in a Fiat-Shamir protocol.
 ///
@@ -10,7 +11,13 @@ use std::{collections::VecDeque, fmt::Debug};
 /// maintains the current transcript for challenge derivation, and supports
 /// hints and proof-of-work (PoW) grinding mechanisms.
 #[derive(Debug)]
-pub struct ProverState<F, EF, Challenger> {
+pub struct ProverState<
+    F,
+    EF,
+    Challenger,
+    const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN,
+    const VECTOR_LEN: usize = LEAN_ISA_VECTOR_LEN,
+> {
     /// Cryptographic challenger used to sample challenges and observe data.
     challenger: Challenger,
 
@@ -18,23 +25,43 @@ pub struct ProverState<F, EF, Challenger> {
     /// to be sent to the verifier.
     proof_data: Vec<F>,
 
-    merkle_hints: VecDeque<Vec<[F; 8]>>,
-    
-    /// Indicates whether padding is used for alignment by LEAN_ISA_VECTOR_LEN (set to true for recursion)
+    /// Hints accumulated during protocol execution, not observed by the challenger.
+    advice: Advice<F, DIGEST_LEN>,
+
+    /// Indicates whether padding is used for alignment by VECTOR_LEN (set to true for recursion)
     padding: bool,
 
     // number of empty field elements, added to simplify the recursive proof, but could be removed to reduce proof size
     n_zeros: usize,
 
+    /// Breakdown of the proof size, by category and label.
+    size_report: ProofSizeReport,
+
+    /// Label attributed to the data currently being sent (e.g. the protocol round).
+    label: Option<String>,
+
+    /// Cost of the transcript operations performed so far, by label.
+    counters: TranscriptCounters,
+
+    /// Whether all hints are bound to the transcript (see `set_hint_binding`).
+    bind_hints: bool,
+
+    /// Bound hints sent since the last challenge, to be observed before the next one.
+    pending_bound_hints: Vec<F>,
+
+    /// Protocol and parameter set bound at creation (see `new_with_protocol`).
+    protocol_id: Option<ProtocolId>,
+
     /// Marker to keep track of the extension field type without storing it explicitly.
     _extension_field: std::marker::PhantomData<EF>,
 }
 
-impl<F, EF, Challenger> ProverState<F, EF, Challenger>
+impl<F, EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize>
+    ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
 where
     EF: ExtensionField<F>,
     F: Field,
-    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F>,
+    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
 {
     /// Create a new prover state with a given domain separator and challenger.
     ///
@@ -49,40 +76,150 @@ where
     where
         Challenger: Clone,
     {
+        const { assert!(VECTOR_LEN > 0, "VECTOR_LEN must be positive") };
         Self {
             challenger,
             proof_data: Vec::new(),
-            merkle_hints: VecDeque::new(),
+            advice: Advice::default(),
             padding,
             n_zeros: 0,
+            size_report: ProofSizeReport::new(std::mem::size_of::<F>()),
+            label: None,
+            counters: TranscriptCounters::default(),
+            bind_hints: false,
+            pending_bound_hints: Vec::new(),
+            protocol_id: None,
             _extension_field: std::marker::PhantomData,
         }
     }
 
+    /// Create a new prover state bound to protocol `name` instantiated with `params`.
+    ///
+    /// The resulting `ProtocolId` is absorbed by the challenger before anything else, and
+    /// stored in the proof so that `VerifierState::new_with_protocol` rejects proofs made
+    /// for a different protocol or parameter set.
+    ///
+    /// # Arguments
+    /// - `challenger`: The initial cryptographic challenger state.
+    /// - `name`: Protocol identifier (e.g. `"whir-v1"`).
+    /// - `params`: Serializable parameter set of the protocol instance.
+    #[must_use]
+    pub fn new_with_protocol(
+        challenger: Challenger,
+        padding: bool,
+        name: &str,
+        params: &impl Serialize,
+    ) -> Self
+    where
+        Challenger: Clone,
+    {
+        let protocol_id = ProtocolId::new(name, params);
+        let mut state = Self::new(challenger, padding);
+        state.observe_bytes(&protocol_id.0);
+        state.protocol_id = Some(protocol_id);
+        state
+    }
+
     pub fn challenger(&self) -> &Challenger {
         &self.challenger
     }
 
     pub fn proof_size(&self) -> usize {
         (self.proof_data.len() - self.n_zeros)
+            + self.advice.scalars.len()
             + self
-                .merkle_hints
+                .advice
+                .merkle_paths
                 .iter()
-                .map(|p| p.len() * LEAN_ISA_VECTOR_LEN)
+                .map(|p| p.len() * DIGEST_LEN)
                 .sum::<usize>()
     }
 
+    /// Breakdown of the proof size accumulated so far, by category and label.
+    pub const fn proof_size_report(&self) -> &ProofSizeReport {
+        &self.size_report
+    }
+
+    /// Cost of the transcript operations performed so far, in total and by label.
+    pub const fn counters(&self) -> &TranscriptCounters {
+        &self.counters
+    }
+
+    /// Attribute the data sent and the transcript operations performed from now on to `label`
+    /// (e.g. a protocol round).
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = Some(label.into());
+    }
+
+    /// Stop attributing the data sent and the transcript operations performed to a label.
+    pub fn clear_label(&mut self) {
+        self.label = None;
+    }
+
+    fn record_size(&mut self, category: ProofSizeCategory, n: usize) {
+        self.size_report.record(self.label.as_deref(), category, n);
+    }
+
+    /// Attribute `counts` to the current label, along with the permutations performed
+    /// since the challenger reported `permutations_before`.
+    fn record_counts(&mut self, permutations_before: Option<usize>, mut counts: TranscriptCounts) {
+        if let (Some(before), Some(after)) = (permutations_before, self.challenger.n_permutations())
+        {
+            counts.permutations = after - before;
+        }
+        self.counters.record(self.label.as_deref(), counts);
+    }
+
+    /// Number of zero lanes following an item of `len` elements in padding mode.
+    const fn padded_lanes(&self, len: usize) -> usize {
+        if self.padding {
+            len.next_multiple_of(VECTOR_LEN) - len
+        } else {
+            0
+        }
+    }
+
+    /// Bind all subsequent hints to the transcript.
+    ///
+    /// When enabled, the hints (including merkle paths) sent since the last challenge are
+    /// observed, prefixed by their number, right before the next challenge is sampled.
+    /// The verifier must enable the same mode at the same point of the protocol.
+    /// Individual hints can also be bound with `bound_hint_base_scalars` /
+    /// `bound_hint_extension_scalars`.
+    ///
+    /// Hints sent after the last challenge are never bound, since no challenge depends on them:
+    /// the verifier must check them directly.
+    pub fn set_hint_binding(&mut self, enabled: bool) {
+        self.bind_hints = enabled;
+    }
+
+    /// Observe the bound hints sent since the last challenge, prefixed by their number.
+    fn bind_pending_hints(&mut self) {
+        if self.pending_bound_hints.is_empty() {
+            return;
+        }
+        let hints = std::mem::take(&mut self.pending_bound_hints);
+        self.observe_public_base(&[F::from_usize(hints.len())]);
+        self.observe_public_base(&hints);
+    }
+
     pub fn has_padding(&self) -> bool {
         self.padding
     }
 
-    pub fn into_proof(self) -> Proof<F> {
+    pub fn into_proof(self) -> Proof<F, DIGEST_LEN> {
         let proof_size = self.proof_size();
         Proof {
             proof_data: self.proof_data,
             padding: self.padding,
+            vector_len: VECTOR_LEN,
             proof_size,
-            merkle_hints: self.merkle_hints,
+            advice: self.advice,
+            protocol_id: self.protocol_id,
+            config: ProofConfig {
+                challenger: self.challenger.kind(),
+                extension_degree: <EF as BasedVectorSpace<F>>::DIMENSION,
+            },
         }
     }
 
@@ -91,11 +228,33 @@ where
     /// # Arguments
     /// - `scalars`: Slice of base field elements to append.
     pub fn add_base_scalars(&mut self, scalars: &[F]) {
+        self.add_padded_base_scalars(scalars, 0);
+    }
+
+    /// Append base field scalars followed by `n_zeros` padding zeros to the transcript,
+    /// and observe all of them in the challenger.
+    fn add_padded_base_scalars(&mut self, scalars: &[F], n_zeros: usize) {
+        self.record_size(ProofSizeCategory::ObservedScalars, scalars.len());
+        self.record_size(ProofSizeCategory::PaddingZeros, n_zeros);
+        self.n_zeros += n_zeros;
+
+        let start = self.proof_data.len();
+
         // Extend the proof data vector with these scalars.
         self.proof_data.extend(scalars);
+        self.proof_data
+            .resize(start + scalars.len() + n_zeros, F::ZERO);
 
         // Notify the challenger that these scalars have been committed.
-        self.challenger.observe_slice(scalars);
+        let permutations = self.challenger.n_permutations();
+        self.challenger.observe_slice(&self.proof_data[start..]);
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                observed: scalars.len() + n_zeros,
+                ..Default::default()
+            },
+        );
     }
 
     /// Append extension field scalars to the transcript.
@@ -105,14 +264,20 @@ where
     /// # Arguments
     /// - `scalars`: Slice of extension field elements to append.
     pub fn add_extension_scalars(&mut self, scalars: &[EF]) {
+        self.add_scalars_in(scalars);
+    }
+
+    /// Append scalars of any extension field `E` of `F` to the transcript (not necessarily `EF`),
+    /// with the same padding as `add_extension_scalars`.
+    ///
+    /// # Arguments
+    /// - `scalars`: Slice of `E` elements to append.
+    pub fn add_scalars_in<E: ExtensionField<F>>(&mut self, scalars: &[E]) {
         // Flatten each extension scalar into base scalars and delegate.
         for ef in scalars {
-            let mut base_scalars = ef.as_basis_coefficients_slice().to_vec();
-            if self.padding {
-                self.n_zeros += LEAN_ISA_VECTOR_LEN - base_scalars.len();
-                base_scalars.resize(LEAN_ISA_VECTOR_LEN, F::ZERO);
-            }
-            self.add_base_scalars(&base_scalars);
+            let base_scalars = ef.as_basis_coefficients_slice();
+            let n_zeros = self.padded_lanes(base_scalars.len());
+            self.add_padded_base_scalars(base_scalars, n_zeros);
         }
     }
 
@@ -125,6 +290,68 @@ where
         self.add_extension_scalars(&[scalar]);
     }
 
+    /// Append a Merkle commitment root to the transcript and observe it in the challenger.
+    ///
+    /// In padding mode, the root is padded with zeros to a multiple of `VECTOR_LEN`.
+    ///
+    /// # Arguments
+    /// - `root`: The commitment root.
+    pub fn add_commitment(&mut self, root: Digest<F, DIGEST_LEN>) {
+        let n_zeros = self.padded_lanes(DIGEST_LEN);
+        self.add_padded_base_scalars(&root.0, n_zeros);
+    }
+
+    /// Observe public base field scalars (e.g. the statement) in the challenger.
+    ///
+    /// Unlike `add_base_scalars`, the scalars are not appended to the proof:
+    /// the verifier is expected to know them and to observe them with `observe_public_base`.
+    ///
+    /// # Arguments
+    /// - `scalars`: Slice of public base field elements to observe.
+    pub fn observe_public_base(&mut self, scalars: &[F]) {
+        let permutations = self.challenger.n_permutations();
+        self.challenger.observe_slice(scalars);
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                observed: scalars.len(),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Observe public bytes (e.g. a protocol name, a circuit hash or a message) in the challenger,
+    /// without appending them to the proof.
+    ///
+    /// The bytes are encoded with `bytes_to_field_elements`, which is length-prefixed, so that
+    /// consecutive calls cannot be confused with each other.
+    pub fn observe_bytes(&mut self, bytes: &[u8]) {
+        self.observe_public_base(&bytes_to_field_elements(bytes));
+    }
+
+    /// Observe a public label in the challenger, encoded as its UTF-8 bytes (see `observe_bytes`).
+    pub fn observe_label(&mut self, label: &str) {
+        self.observe_bytes(label.as_bytes());
+    }
+
+    /// Observe public extension field scalars in the challenger, without appending them to the proof.
+    ///
+    /// In padding mode, each scalar is padded with zeros to a multiple of `VECTOR_LEN`,
+    /// as in `add_extension_scalars`.
+    ///
+    /// # Arguments
+    /// - `scalars`: Slice of public extension field elements to observe.
+    pub fn observe_public_extension(&mut self, scalars: &[EF]) {
+        for ef in scalars {
+            let mut base_scalars = ef.as_basis_coefficients_slice().to_vec();
+            base_scalars.resize(
+                base_scalars.len() + self.padded_lanes(base_scalars.len()),
+                F::ZERO,
+            );
+            self.observe_public_base(&base_scalars);
+        }
+    }
+
     /// Append base field scalars to the transcript as hints.
     ///
     /// Unlike `add_base_scalars`, hints are not observed by the challenger.
@@ -132,13 +359,36 @@ where
     /// # Arguments
     /// - `scalars`: Slice of base field elements to append.
     pub fn hint_base_scalars(&mut self, scalars: &[F]) {
-        assert!(scalars.len() % LEAN_ISA_VECTOR_LEN == 0);
-        // Only extend proof data, no challenger observation.
-        self.proof_data.extend(scalars);
+        assert!(scalars.len() % VECTOR_LEN == 0);
+        self.push_hint_scalars(scalars, self.bind_hints);
+    }
+
+    /// Append base field scalars to the transcript as hints, bound to the transcript:
+    /// they are observed right before the next challenge is sampled.
+    ///
+    /// # Arguments
+    /// - `scalars`: Slice of base field elements to append.
+    pub fn bound_hint_base_scalars(&mut self, scalars: &[F]) {
+        assert!(scalars.len() % VECTOR_LEN == 0);
+        self.push_hint_scalars(scalars, true);
+    }
+
+    fn push_hint_scalars(&mut self, scalars: &[F], bound: bool) {
+        self.record_size(ProofSizeCategory::Hints, scalars.len());
+        // Only extend the advice, no challenger observation.
+        self.advice.scalars.extend(scalars);
+        if bound {
+            self.pending_bound_hints.extend(scalars);
+        }
     }
 
-    pub fn hint_merkle_path(&mut self, path: Vec<[F; 8]>) {
-        self.merkle_hints.push_back(path);
+    pub fn hint_merkle_path(&mut self, path: Vec<Digest<F, DIGEST_LEN>>) {
+        self.record_size(ProofSizeCategory::MerklePaths, path.len() * DIGEST_LEN);
+        if self.bind_hints {
+            self.pending_bound_hints
+                .extend(path.iter().flat_map(|node| node.0));
+        }
+        self.advice.merkle_paths.push_back(path);
     }
 
     /// Append extension field scalars to the transcript as hints.
@@ -146,9 +396,19 @@ where
     /// # Arguments
     /// - `scalars`: Slice of extension field elements to append.
     pub fn hint_extension_scalars(&mut self, scalars: &[EF]) {
-        assert!(scalars.len() % LEAN_ISA_VECTOR_LEN == 0);
+        assert!(scalars.len() % VECTOR_LEN == 0);
         // Flatten extension field scalars and append as base field scalars.
-        self.proof_data.extend(flatten_scalars_to_base(scalars));
+        self.push_hint_scalars(&flatten_scalars_to_base(scalars), self.bind_hints);
+    }
+
+    /// Append extension field scalars to the transcript as hints, bound to the transcript:
+    /// they are observed right before the next challenge is sampled.
+    ///
+    /// # Arguments
+    /// - `scalars`: Slice of extension field elements to append.
+    pub fn bound_hint_extension_scalars(&mut self, scalars: &[EF]) {
+        assert!(scalars.len() % VECTOR_LEN == 0);
+        self.push_hint_scalars(&flatten_scalars_to_base(scalars), true);
     }
 
     /// Sample a new random extension field element from the challenger.
@@ -156,7 +416,25 @@ where
     /// # Returns
     /// A new challenge element in the extension field.
     pub fn sample(&mut self) -> EF {
-        self.challenger.sample_algebra_element()
+        self.sample_in()
+    }
+
+    /// Sample a new random element of any extension field `E` of `F` from the challenger.
+    ///
+    /// # Returns
+    /// A new challenge element in `E`.
+    pub fn sample_in<E: ExtensionField<F>>(&mut self) -> E {
+        self.bind_pending_hints();
+        let permutations = self.challenger.n_permutations();
+        let challenge = self.challenger.sample_algebra_element();
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                squeezed: <E as BasedVectorSpace<F>>::DIMENSION,
+                ..Default::default()
+            },
+        );
+        challenge
     }
 
     pub fn sample_vec(&mut self, len: usize) -> Vec<EF> {
@@ -171,7 +449,17 @@ where
     /// # Returns
     /// A uniformly random value with `bits` bits.
     pub fn sample_bits(&mut self, bits: usize) -> usize {
-        self.challenger.sample_bits(bits)
+        self.bind_pending_hints();
+        let permutations = self.challenger.n_permutations();
+        let res = self.challenger.sample_bits(bits);
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                squeezed: 1,
+                ..Default::default()
+            },
+        );
+        res
     }
 
     /// Perform PoW grinding and append the witness to the transcript.
@@ -184,13 +472,31 @@ where
             return;
         }
 
+        self.bind_pending_hints();
+
         // Perform grinding and obtain a witness element in the base field.
+        let permutations = self.challenger.n_permutations();
         let witness = self.challenger.grind(bits);
+        let grinding_attempts = match (permutations, self.challenger.n_permutations()) {
+            (Some(before), Some(after)) => after - before,
+            _ => 0,
+        };
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                observed: 1,
+                squeezed: 1,
+                grinding_attempts,
+                ..Default::default()
+            },
+        );
 
         // Append the witness to the proof data.
+        self.record_size(ProofSizeCategory::GrindingWitnesses, 1);
         self.proof_data.push(witness);
         if self.padding {
-            for _ in 0..LEAN_ISA_VECTOR_LEN - 1 {
+            self.record_size(ProofSizeCategory::PaddingZeros, VECTOR_LEN - 1);
+            for _ in 0..VECTOR_LEN - 1 {
                 self.proof_data.push(F::ZERO);
                 self.n_zeros += 1;
             }
@@ -198,11 +504,12 @@ where
     }
 }
 
-impl<F, EF, Challenger> ChallengeSampler<EF> for ProverState<F, EF, Challenger>
+impl<F, EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize> ChallengeSampler<EF>
+    for ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
 where
     EF: ExtensionField<F>,
     F: Field,
-    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F>,
+    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
 {
     fn sample_bits(&mut self, bits: usize) -> usize {
         self.sample_bits(bits)
@@ -0,0 +1,96 @@
+use p3_challenger::{FieldChallenger, GrindingChallenger};
+use p3_field::{ExtensionField, Field};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Label absorbed before the public key and the message of a signature of knowledge,
+/// separating signatures from plain proofs.
+pub const SIGNATURE_LABEL: &str = "fiat-shamir/signature-of-knowledge";
+
+/// A signature of knowledge: a proof whose transcript binds a public key and a message
+/// (see `ProverState::new_signing`).
+///
+/// The proof is stored in its wire encoding, without padding zeros.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Signature<F, const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN> {
+    pub proof: WireProof<F, DIGEST_LEN>,
+}
+
+impl<F: Field, const DIGEST_LEN: usize> From<Proof<F, DIGEST_LEN>> for Signature<F, DIGEST_LEN> {
+    fn from(proof: Proof<F, DIGEST_LEN>) -> Self {
+        Self {
+            proof: proof.to_wire(),
+        }
+    }
+}
+
+impl<F, EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize>
+    ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
+where
+    EF: ExtensionField<F>,
+    F: Field,
+    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState + Clone,
+{
+    /// Create a prover state producing a signature of knowledge on `message` under
+    /// `public_key`: both are absorbed by the challenger before anything else.
+    ///
+    /// # Arguments
+    /// - `challenger`: The initial cryptographic challenger state.
+    /// - `public_key`: Encoded public key of the signer.
+    /// - `message`: The signed message.
+    #[must_use]
+    pub fn new_signing(
+        challenger: Challenger,
+        padding: bool,
+        public_key: &[u8],
+        message: &[u8],
+    ) -> Self {
+        let mut state = Self::new(challenger, padding);
+        state.observe_label(SIGNATURE_LABEL);
+        state.observe_bytes(public_key);
+        state.observe_bytes(message);
+        state
+    }
+
+    pub fn into_signature(self) -> Signature<F, DIGEST_LEN> {
+        self.into_proof().into()
+    }
+}
+
+impl<F, EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize>
+    VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
+where
+    EF: ExtensionField<F>,
+    F: Field,
+    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
+{
+    /// Create a verifier state for a signature of knowledge on `message` under `public_key`,
+    /// mirroring `ProverState::new_signing`.
+    ///
+    /// The signature is decoded and verified within `limits`.
+    ///
+    /// # Errors
+    /// Returns `ProofError::LimitExceeded` if the signature exceeds `limits`,
+    /// `ProofError::InvalidProof` if its encoding is malformed,
+    /// or `ProofError::PaddingMismatch` if it was padded to a different vector length.
+    pub fn new_signature(
+        signature: Signature<F, DIGEST_LEN>,
+        challenger: Challenger,
+        public_key: &[u8],
+        message: &[u8],
+        limits: VerifierLimits,
+    ) -> Result<Self, ProofError> {
+        let proof = signature.proof.into_proof(&limits)?;
+        let mut state = Self::new_with_limits(proof, challenger, limits)?;
+        state.observe_signing_context(public_key, message);
+        Ok(state)
+    }
+
+    /// Absorb the signing context, as `ProverState::new_signing` does.
+    pub(crate) fn observe_signing_context(&mut self, public_key: &[u8], message: &[u8]) {
+        self.observe_label(SIGNATURE_LABEL);
+        self.observe_bytes(public_key);
+        self.observe_bytes(message);
+    }
+}
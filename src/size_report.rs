@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Category of the field elements making up a proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProofSizeCategory {
+    /// Scalars observed by the challenger (messages, commitments).
+    ObservedScalars,
+    /// Scalars sent as hints (not observed).
+    Hints,
+    /// Merkle authentication paths.
+    MerklePaths,
+    /// Proof-of-work grinding witnesses.
+    GrindingWitnesses,
+    /// Zeros added for alignment by the vector length in padding mode.
+    PaddingZeros,
+}
+
+/// Number of field elements per [`ProofSizeCategory`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProofSizeBreakdown {
+    pub observed_scalars: usize,
+    pub hints: usize,
+    pub merkle_paths: usize,
+    pub grinding_witnesses: usize,
+    pub padding_zeros: usize,
+}
+
+impl ProofSizeBreakdown {
+    fn add(&mut self, category: ProofSizeCategory, n: usize) {
+        match category {
+            ProofSizeCategory::ObservedScalars => self.observed_scalars += n,
+            ProofSizeCategory::Hints => self.hints += n,
+            ProofSizeCategory::MerklePaths => self.merkle_paths += n,
+            ProofSizeCategory::GrindingWitnesses => self.grinding_witnesses += n,
+            ProofSizeCategory::PaddingZeros => self.padding_zeros += n,
+        }
+    }
+
+    /// Number of field elements, excluding padding zeros (same convention as `proof_size`).
+    pub const fn proof_size(&self) -> usize {
+        self.observed_scalars + self.hints + self.merkle_paths + self.grinding_witnesses
+    }
+
+    /// Number of field elements, including padding zeros.
+    pub const fn total_elements(&self) -> usize {
+        self.proof_size() + self.padding_zeros
+    }
+}
+
+/// Breakdown of a proof's size, by category and by label.
+///
+/// Labels are set on the prover with `ProverState::set_label`, typically once per round.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofSizeReport {
+    /// Size of a field element, in bytes.
+    pub field_bytes: usize,
+    /// Breakdown over the whole proof.
+    pub total: ProofSizeBreakdown,
+    /// Breakdown of the elements sent while each label was active.
+    pub by_label: BTreeMap<String, ProofSizeBreakdown>,
+}
+
+impl ProofSizeReport {
+    #[must_use]
+    pub fn new(field_bytes: usize) -> Self {
+        Self {
+            field_bytes,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn record(&mut self, label: Option<&str>, category: ProofSizeCategory, n: usize) {
+        self.total.add(category, n);
+        if let Some(label) = label {
+            self.by_label
+                .entry(label.to_string())
+                .or_default()
+                .add(category, n);
+        }
+    }
+
+    /// Convert a number of field elements to bytes.
+    pub const fn bytes(&self, n_elements: usize) -> usize {
+        n_elements * self.field_bytes
+    }
+
+    /// Proof size in bytes, excluding padding zeros.
+    pub const fn proof_size_bytes(&self) -> usize {
+        self.bytes(self.total.proof_size())
+    }
+}
@@ -0,0 +1,64 @@
+use p3_field::{ExtensionField, Field};
+
+use crate::*;
+
+/// Evaluate the polynomial with coefficients `coeffs` (constant term first) at `point`.
+fn eval_polynomial<EF: Field>(coeffs: &[EF], point: EF) -> EF {
+    coeffs
+        .iter()
+        .rev()
+        .fold(EF::ZERO, |acc, &coeff| acc * point + coeff)
+}
+
+/// Send (prover) or receive (verifier) a sumcheck round polynomial `p` of degree `degree`, with
+/// `p(0) + p(1) = claim`, and sample the round challenge.
+///
+/// The linear coefficient is implied by the claim, so only `c_0, c_2, ..., c_d` are sent, and
+/// both sides reconstruct `c_1` from the running claim: `p(0) + p(1) = claim` holds by
+/// construction on the verifier side.
+///
+/// # Arguments
+/// - `transcript`: The prover or verifier transcript (possibly a dyn transcript).
+/// - `degree`: Degree of the round polynomial (at least 1).
+/// - `claim`: The running claim `p(0) + p(1)`.
+/// - `coeffs`: Coefficients of `p`, constant term first, only evaluated on the prover side.
+///
+/// # Returns
+/// The challenge `r` and the next claim `p(r)`.
+///
+/// # Errors
+/// Returns `ProofError::ExceededTranscript` if the verifier has insufficient data remaining.
+pub fn sumcheck_round<EF, T>(
+    transcript: &mut T,
+    degree: usize,
+    claim: EF,
+    coeffs: impl FnOnce() -> Vec<EF>,
+) -> ProofResult<(EF, EF)>
+where
+    EF: ExtensionField<PF<EF>>,
+    T: Transcript<EF> + ?Sized,
+{
+    assert!(degree >= 1, "Round polynomial must have degree at least 1");
+    let compressed = transcript.send_or_receive_extension(degree, || {
+        let coeffs = coeffs();
+        assert_eq!(
+            coeffs.len(),
+            degree + 1,
+            "Round polynomial has the wrong degree"
+        );
+        assert_eq!(
+            eval_polynomial(&coeffs, EF::ZERO) + eval_polynomial(&coeffs, EF::ONE),
+            claim,
+            "Round polynomial is inconsistent with the claim"
+        );
+        [&coeffs[..1], &coeffs[2..]].concat()
+    })?;
+
+    // p(0) + p(1) = 2 c_0 + c_1 + c_2 + ... + c_d
+    let c0 = compressed[0];
+    let c1 = claim - c0.double() - compressed[1..].iter().copied().sum::<EF>();
+    let coeffs = [&[c0, c1], &compressed[1..]].concat();
+
+    let challenge = transcript.sample();
+    Ok((challenge, eval_polynomial(&coeffs, challenge)))
+}
@@ -0,0 +1,189 @@
+use std::{
+    fmt::Display,
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use crate::*;
+use p3_challenger::{FieldChallenger, GrindingChallenger};
+use p3_field::{ExtensionField, Field};
+
+/// A single modification applied to an honest proof by [`find_accepted_mutations`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ProofMutation {
+    /// `proof_data[index]` was incremented by one.
+    ProofData { index: usize },
+    /// `advice.scalars[index]` was incremented by one.
+    AdviceScalar { index: usize },
+    /// The first lane of `advice.merkle_paths[path][node]` was incremented by one.
+    MerkleHint { path: usize, node: usize },
+    /// The last element of `proof_data` was removed.
+    TruncateProofData,
+    /// The last advice scalar was removed.
+    TruncateAdvice,
+    /// The last merkle path was removed.
+    TruncateMerkleHints,
+    /// A zero element was appended to `proof_data`.
+    ExtendProofData,
+    /// A zero element was appended to the advice scalars.
+    ExtendAdvice,
+    /// An empty merkle path was appended to the advice.
+    ExtendMerkleHints,
+    /// The `padding` flag was flipped.
+    FlipPadding,
+}
+
+impl Display for ProofMutation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ProofData { index } => write!(f, "proof_data[{index}] modified"),
+            Self::AdviceScalar { index } => write!(f, "advice.scalars[{index}] modified"),
+            Self::MerkleHint { path, node } => {
+                write!(f, "advice.merkle_paths[{path}][{node}] modified")
+            }
+            Self::TruncateProofData => write!(f, "proof_data truncated"),
+            Self::TruncateAdvice => write!(f, "advice.scalars truncated"),
+            Self::TruncateMerkleHints => write!(f, "advice.merkle_paths truncated"),
+            Self::ExtendProofData => write!(f, "proof_data extended"),
+            Self::ExtendAdvice => write!(f, "advice.scalars extended"),
+            Self::ExtendMerkleHints => write!(f, "advice.merkle_paths extended"),
+            Self::FlipPadding => write!(f, "padding flipped"),
+        }
+    }
+}
+
+/// Outcome of a tampering run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TamperReport {
+    /// Number of mutated proofs submitted to the verifier.
+    pub n_mutations: usize,
+    /// Mutations the verifier still accepted.
+    pub accepted: Vec<ProofMutation>,
+}
+
+impl TamperReport {
+    /// Returns `true` if every mutation was rejected.
+    pub fn all_rejected(&self) -> bool {
+        self.accepted.is_empty()
+    }
+}
+
+/// All the mutations that [`find_accepted_mutations`] applies to `proof`.
+pub fn proof_mutations<F, const DIGEST_LEN: usize>(
+    proof: &Proof<F, DIGEST_LEN>,
+) -> Vec<ProofMutation> {
+    let mut mutations = (0..proof.proof_data.len())
+        .map(|index| ProofMutation::ProofData { index })
+        .collect::<Vec<_>>();
+    mutations
+        .extend((0..proof.advice.scalars.len()).map(|index| ProofMutation::AdviceScalar { index }));
+    for (path, nodes) in proof.advice.merkle_paths.iter().enumerate() {
+        mutations.extend((0..nodes.len()).map(|node| ProofMutation::MerkleHint { path, node }));
+    }
+    if !proof.proof_data.is_empty() {
+        mutations.push(ProofMutation::TruncateProofData);
+    }
+    if !proof.advice.scalars.is_empty() {
+        mutations.push(ProofMutation::TruncateAdvice);
+    }
+    if !proof.advice.merkle_paths.is_empty() {
+        mutations.push(ProofMutation::TruncateMerkleHints);
+    }
+    mutations.extend([
+        ProofMutation::ExtendProofData,
+        ProofMutation::ExtendAdvice,
+        ProofMutation::ExtendMerkleHints,
+        ProofMutation::FlipPadding,
+    ]);
+    mutations
+}
+
+/// Apply `mutation` to a copy of `proof`.
+pub fn mutate_proof<F: Field, const DIGEST_LEN: usize>(
+    proof: &Proof<F, DIGEST_LEN>,
+    mutation: &ProofMutation,
+) -> Proof<F, DIGEST_LEN> {
+    let mut proof = proof.clone();
+    match *mutation {
+        ProofMutation::ProofData { index } => proof.proof_data[index] += F::ONE,
+        ProofMutation::AdviceScalar { index } => proof.advice.scalars[index] += F::ONE,
+        ProofMutation::MerkleHint { path, node } => {
+            proof.advice.merkle_paths[path][node].0[0] += F::ONE;
+        }
+        ProofMutation::TruncateProofData => {
+            proof.proof_data.pop();
+        }
+        ProofMutation::TruncateAdvice => {
+            proof.advice.scalars.pop();
+        }
+        ProofMutation::TruncateMerkleHints => {
+            proof.advice.merkle_paths.pop_back();
+        }
+        ProofMutation::ExtendProofData => proof.proof_data.push(F::ZERO),
+        ProofMutation::ExtendAdvice => proof.advice.scalars.push(F::ZERO),
+        ProofMutation::ExtendMerkleHints => proof.advice.merkle_paths.push_back(Vec::new()),
+        ProofMutation::FlipPadding => proof.padding = !proof.padding,
+    }
+    proof
+}
+
+/// Produce an honest proof with `prove`, then submit every mutation of it to `verify` and
+/// report the ones that are still accepted.
+///
+/// After `verify` succeeds, `VerifierState::finish` checks that the whole proof was consumed.
+/// A panic inside `verify` counts as a rejection.
+///
+/// # Arguments
+/// - `challenger`: The initial challenger, cloned for the prover and each verification.
+/// - `padding`: Whether the prover pads to `VECTOR_LEN`.
+/// - `prove`: The prover side of the protocol.
+/// - `verify`: The verifier side of the protocol.
+///
+/// # Panics
+/// Panics if the honest proof is rejected.
+pub fn find_accepted_mutations<
+    F,
+    EF,
+    Challenger,
+    P,
+    V,
+    const DIGEST_LEN: usize,
+    const VECTOR_LEN: usize,
+>(
+    challenger: Challenger,
+    padding: bool,
+    prove: P,
+    verify: V,
+) -> TamperReport
+where
+    EF: ExtensionField<F>,
+    F: Field,
+    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState + Clone,
+    P: FnOnce(&mut ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>),
+    V: Fn(&mut VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>) -> ProofResult<()>,
+{
+    let mut prover = ProverState::new(challenger.clone(), padding);
+    prove(&mut prover);
+    let proof = prover.into_proof();
+
+    let accepts = |proof: Proof<F, DIGEST_LEN>| {
+        catch_unwind(AssertUnwindSafe(|| {
+            VerifierState::try_new(proof, challenger.clone())
+                .and_then(|mut verifier| {
+                    verify(&mut verifier)?;
+                    verifier.finish()
+                })
+                .is_ok()
+        }))
+        .unwrap_or(false)
+    };
+    assert!(accepts(proof.clone()), "honest proof was rejected");
+
+    let mutations = proof_mutations(&proof);
+    TamperReport {
+        n_mutations: mutations.len(),
+        accepted: mutations
+            .into_iter()
+            .filter(|mutation| accepts(mutate_proof(&proof, mutation)))
+            .collect(),
+    }
+}
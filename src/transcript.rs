@@ -0,0 +1,136 @@
+use p3_challenger::{FieldChallenger, GrindingChallenger};
+use p3_field::{ExtensionField, Field};
+
+use crate::*;
+
+/// Operations shared by the prover and the verifier, so that protocol skeletons (e.g. sumcheck
+/// rounds) can be written once and instantiated for either role.
+///
+/// Messages are given as closures, only evaluated on the prover side: the prover computes,
+/// sends and returns them, while the verifier reads them from the proof.
+pub trait Transcript<EF: ExtensionField<PF<EF>>>: ChallengeSampler<EF> {
+    /// Whether this is the prover side of the transcript.
+    const IS_PROVER: bool;
+
+    /// Send (prover) or receive (verifier) `n` observed base scalars.
+    ///
+    /// # Errors
+    /// Returns the verifier's error if the proof data cannot be read.
+    fn send_or_receive_base(
+        &mut self,
+        n: usize,
+        scalars: impl FnOnce() -> Vec<PF<EF>>,
+    ) -> ProofResult<Vec<PF<EF>>>;
+
+    /// Send (prover) or receive (verifier) `n` observed extension scalars.
+    ///
+    /// # Errors
+    /// Returns the verifier's error if the proof data cannot be read.
+    fn send_or_receive_extension(
+        &mut self,
+        n: usize,
+        scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>>;
+
+    /// Send (prover) or receive (verifier) `n` extension scalars as hints.
+    ///
+    /// # Errors
+    /// Returns the verifier's error if the advice cannot be read.
+    fn hint_or_receive(
+        &mut self,
+        n: usize,
+        scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>>;
+
+    /// Grind (prover) or check the grinding witness (verifier) for `bits` bits.
+    ///
+    /// # Errors
+    /// Returns the verifier's error if the witness is missing or invalid.
+    fn grind_or_check(&mut self, bits: usize) -> ProofResult<()>;
+}
+
+impl<EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize> Transcript<EF>
+    for ProverState<PF<EF>, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
+where
+    EF: ExtensionField<PF<EF>>,
+    PF<EF>: Field,
+    Challenger: FieldChallenger<PF<EF>> + GrindingChallenger<Witness = PF<EF>> + ChallengerState,
+{
+    const IS_PROVER: bool = true;
+
+    fn send_or_receive_base(
+        &mut self,
+        n: usize,
+        scalars: impl FnOnce() -> Vec<PF<EF>>,
+    ) -> ProofResult<Vec<PF<EF>>> {
+        let scalars = scalars();
+        assert_eq!(scalars.len(), n);
+        self.add_base_scalars(&scalars);
+        Ok(scalars)
+    }
+
+    fn send_or_receive_extension(
+        &mut self,
+        n: usize,
+        scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>> {
+        let scalars = scalars();
+        assert_eq!(scalars.len(), n);
+        self.add_extension_scalars(&scalars);
+        Ok(scalars)
+    }
+
+    fn hint_or_receive(
+        &mut self,
+        n: usize,
+        scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>> {
+        let scalars = scalars();
+        assert_eq!(scalars.len(), n);
+        self.hint_extension_scalars(&scalars);
+        Ok(scalars)
+    }
+
+    fn grind_or_check(&mut self, bits: usize) -> ProofResult<()> {
+        self.pow_grinding(bits);
+        Ok(())
+    }
+}
+
+impl<EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize> Transcript<EF>
+    for VerifierState<PF<EF>, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
+where
+    EF: ExtensionField<PF<EF>>,
+    PF<EF>: Field,
+    Challenger: FieldChallenger<PF<EF>> + GrindingChallenger<Witness = PF<EF>> + ChallengerState,
+{
+    const IS_PROVER: bool = false;
+
+    fn send_or_receive_base(
+        &mut self,
+        n: usize,
+        _scalars: impl FnOnce() -> Vec<PF<EF>>,
+    ) -> ProofResult<Vec<PF<EF>>> {
+        self.next_base_scalars_vec(n)
+    }
+
+    fn send_or_receive_extension(
+        &mut self,
+        n: usize,
+        _scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>> {
+        self.next_extension_scalars_vec(n)
+    }
+
+    fn hint_or_receive(
+        &mut self,
+        n: usize,
+        _scalars: impl FnOnce() -> Vec<EF>,
+    ) -> ProofResult<Vec<EF>> {
+        self.receive_hint_extension_scalars(n)
+    }
+
+    fn grind_or_check(&mut self, bits: usize) -> ProofResult<()> {
+        self.check_pow_grinding(bits)
+    }
+}
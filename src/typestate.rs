@@ -0,0 +1,275 @@
+//! Typestate layer over `ProverState` / `VerifierState`.
+//!
+//! A protocol is described once as a type-level list of steps, shared by both sides:
+//!
+//! ```
+//! use fiat_shamir::*;
+//! use p3_challenger::DuplexChallenger;
+//! use p3_field::{PrimeCharacteristicRing, extension::BinomialExtensionField};
+//! use p3_koala_bear::{KoalaBear, default_koalabear_poseidon2_16};
+//!
+//! type F = KoalaBear;
+//! type EF = BinomialExtensionField<F, 4>;
+//! type Round = SendExtension<2, SampleExtension<1, Grind<8, Done>>>;
+//!
+//! let challenger = || DuplexChallenger::<F, _, 16, 8>::new(default_koalabear_poseidon2_16());
+//!
+//! let prover = ProverState::<F, EF, _>::new(challenger(), false);
+//! let ([challenge], prover) = TypedProver::<_, Round>::new(prover)
+//!     .send([EF::ONE, EF::TWO])
+//!     .sample();
+//! let proof = prover.grind().finish().into_proof();
+//!
+//! let verifier = VerifierState::<F, EF, _>::new(proof, challenger());
+//! let verifier = TypedVerifier::<_, Round>::new(verifier);
+//! let (scalars, verifier) = verifier.receive()?;
+//! let ([verifier_challenge], verifier) = verifier.sample()?;
+//! assert_eq!(scalars, [EF::ONE, EF::TWO]);
+//! assert_eq!(verifier_challenge, challenge);
+//! verifier.check_grinding()?.finish()?;
+//! # Ok::<(), ProofError>(())
+//! ```
+//!
+//! `TypedProver<_, Round>` and `TypedVerifier<_, Round>` only expose the method of the current
+//! step, which consumes the state and returns the state of the next step. Sampling before
+//! sending the required message, or receiving base scalars where extension scalars were sent,
+//! does not compile:
+//!
+//! ```compile_fail
+//! # use fiat_shamir::*;
+//! # use p3_challenger::DuplexChallenger;
+//! # use p3_field::extension::BinomialExtensionField;
+//! # use p3_koala_bear::{KoalaBear, default_koalabear_poseidon2_16};
+//! # type F = KoalaBear;
+//! # type EF = BinomialExtensionField<F, 4>;
+//! # type Round = SendExtension<2, SampleExtension<1, Grind<8, Done>>>;
+//! # let challenger = DuplexChallenger::<F, _, 16, 8>::new(default_koalabear_poseidon2_16());
+//! let prover = TypedProver::<_, Round>::new(ProverState::<F, EF, _>::new(challenger, false));
+//! let ([challenge], prover) = prover.sample();
+//! ```
+//!
+//! Hint steps must hint a multiple of the vector length, which is checked at compile time.
+
+use std::marker::PhantomData;
+
+use p3_challenger::{FieldChallenger, GrindingChallenger};
+use p3_field::{ExtensionField, Field};
+
+use crate::*;
+
+/// Send `N` base scalars (observed).
+#[derive(Debug)]
+pub struct SendBase<const N: usize, Next>(PhantomData<Next>);
+
+/// Send `N` extension scalars (observed).
+#[derive(Debug)]
+pub struct SendExtension<const N: usize, Next>(PhantomData<Next>);
+
+/// Send `N` base scalars as hints (not observed).
+#[derive(Debug)]
+pub struct HintBase<const N: usize, Next>(PhantomData<Next>);
+
+/// Send `N` extension scalars as hints (not observed).
+#[derive(Debug)]
+pub struct HintExtension<const N: usize, Next>(PhantomData<Next>);
+
+/// Sample `N` extension field challenges.
+#[derive(Debug)]
+pub struct SampleExtension<const N: usize, Next>(PhantomData<Next>);
+
+/// Sample a `BITS`-bit integer.
+#[derive(Debug)]
+pub struct SampleBits<const BITS: usize, Next>(PhantomData<Next>);
+
+/// Proof of work of `BITS` bits.
+#[derive(Debug)]
+pub struct Grind<const BITS: usize, Next>(PhantomData<Next>);
+
+/// End of the protocol.
+#[derive(Debug)]
+pub struct Done;
+
+/// A `ProverState` at step `Spec` of a protocol.
+#[derive(Debug)]
+pub struct TypedProver<State, Spec> {
+    state: State,
+    _spec: PhantomData<Spec>,
+}
+
+/// A `VerifierState` at step `Spec` of a protocol.
+#[derive(Debug)]
+pub struct TypedVerifier<State, Spec> {
+    state: State,
+    _spec: PhantomData<Spec>,
+}
+
+impl<State, Spec> TypedProver<State, Spec> {
+    /// Start protocol `Spec` on a prover state.
+    pub const fn new(state: State) -> Self {
+        Self {
+            state,
+            _spec: PhantomData,
+        }
+    }
+
+    const fn next<Next>(state: State) -> TypedProver<State, Next> {
+        TypedProver {
+            state,
+            _spec: PhantomData,
+        }
+    }
+}
+
+impl<State, Spec> TypedVerifier<State, Spec> {
+    /// Start protocol `Spec` on a verifier state.
+    pub const fn new(state: State) -> Self {
+        Self {
+            state,
+            _spec: PhantomData,
+        }
+    }
+
+    const fn next<Next>(state: State) -> TypedVerifier<State, Next> {
+        TypedVerifier {
+            state,
+            _spec: PhantomData,
+        }
+    }
+}
+
+/// Implement a prover and a verifier step for each spec, with the `ProverState` / `VerifierState`
+/// bounds.
+macro_rules! typed_steps {
+    ($(
+        $spec:ident<$n:ident> {
+            prover: $prover_fn:ident($prover_self:ident $(, $arg:ident: $arg_ty:ty)?) -> $prover_out:ty $prover_body:block
+            verifier: $verifier_fn:ident($verifier_self:ident) -> $verifier_out:ty $verifier_body:block
+        }
+    )*) => {$(
+        impl<F, EF, Challenger, Next, const $n: usize, const DIGEST_LEN: usize, const VECTOR_LEN: usize>
+            TypedProver<ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, $spec<$n, Next>>
+        where
+            EF: ExtensionField<F>,
+            F: Field,
+            Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
+        {
+            pub fn $prover_fn(
+                mut $prover_self $(, $arg: $arg_ty)?
+            ) -> $prover_out $prover_body
+        }
+
+        impl<F, EF, Challenger, Next, const $n: usize, const DIGEST_LEN: usize, const VECTOR_LEN: usize>
+            TypedVerifier<VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, $spec<$n, Next>>
+        where
+            EF: ExtensionField<F>,
+            F: Field,
+            Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
+        {
+            /// # Errors
+            /// Returns the error of the underlying `VerifierState` operation.
+            pub fn $verifier_fn(
+                mut $verifier_self
+            ) -> Result<$verifier_out, ProofError> $verifier_body
+        }
+    )*};
+}
+
+typed_steps! {
+    SendBase<N> {
+        prover: send(self, scalars: [F; N]) -> TypedProver<ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next> {
+            self.state.add_base_scalars(&scalars);
+            Self::next(self.state)
+        }
+        verifier: receive(self) -> ([F; N], TypedVerifier<VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next>) {
+            let scalars = self.state.next_base_scalars_const::<N>()?;
+            Ok((scalars, Self::next(self.state)))
+        }
+    }
+    SendExtension<N> {
+        prover: send(self, scalars: [EF; N]) -> TypedProver<ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next> {
+            self.state.add_extension_scalars(&scalars);
+            Self::next(self.state)
+        }
+        verifier: receive(self) -> ([EF; N], TypedVerifier<VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next>) {
+            let scalars = self.state.next_extension_scalars_const::<N>()?;
+            Ok((scalars, Self::next(self.state)))
+        }
+    }
+    HintBase<N> {
+        prover: hint(self, scalars: [F; N]) -> TypedProver<ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next> {
+            const { assert!(N % VECTOR_LEN == 0, "hints must be a multiple of VECTOR_LEN") };
+            self.state.hint_base_scalars(&scalars);
+            Self::next(self.state)
+        }
+        verifier: receive_hint(self) -> ([F; N], TypedVerifier<VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next>) {
+            const { assert!(N % VECTOR_LEN == 0, "hints must be a multiple of VECTOR_LEN") };
+            let scalars = self.state.receive_hint_base_scalars(N)?.try_into().unwrap();
+            Ok((scalars, Self::next(self.state)))
+        }
+    }
+    HintExtension<N> {
+        prover: hint(self, scalars: [EF; N]) -> TypedProver<ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next> {
+            const { assert!(N % VECTOR_LEN == 0, "hints must be a multiple of VECTOR_LEN") };
+            self.state.hint_extension_scalars(&scalars);
+            Self::next(self.state)
+        }
+        verifier: receive_hint(self) -> ([EF; N], TypedVerifier<VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next>) {
+            const { assert!(N % VECTOR_LEN == 0, "hints must be a multiple of VECTOR_LEN") };
+            let scalars = self.state.receive_hint_extension_scalars(N)?.try_into().unwrap();
+            Ok((scalars, Self::next(self.state)))
+        }
+    }
+    SampleExtension<N> {
+        prover: sample(self) -> ([EF; N], TypedProver<ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next>) {
+            let challenges = std::array::from_fn(|_| self.state.sample());
+            (challenges, Self::next(self.state))
+        }
+        verifier: sample(self) -> ([EF; N], TypedVerifier<VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next>) {
+            let challenges = std::array::from_fn(|_| self.state.sample());
+            Ok((challenges, Self::next(self.state)))
+        }
+    }
+    SampleBits<BITS> {
+        prover: sample_bits(self) -> (usize, TypedProver<ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next>) {
+            let value = self.state.sample_bits(BITS);
+            (value, Self::next(self.state))
+        }
+        verifier: sample_bits(self) -> (usize, TypedVerifier<VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next>) {
+            let value = self.state.sample_bits(BITS);
+            Ok((value, Self::next(self.state)))
+        }
+    }
+    Grind<BITS> {
+        prover: grind(self) -> TypedProver<ProverState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next> {
+            self.state.pow_grinding(BITS);
+            Self::next(self.state)
+        }
+        verifier: check_grinding(self) -> TypedVerifier<VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Next> {
+            self.state.check_pow_grinding(BITS)?;
+            Ok(Self::next(self.state))
+        }
+    }
+}
+
+impl<State> TypedProver<State, Done> {
+    /// The underlying prover state, once the protocol is complete.
+    pub fn finish(self) -> State {
+        self.state
+    }
+}
+
+impl<F, EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize>
+    TypedVerifier<VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>, Done>
+where
+    EF: ExtensionField<F>,
+    F: Field,
+    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
+{
+    /// End the protocol, checking that the whole proof was consumed (see `VerifierState::finish`).
+    ///
+    /// # Errors
+    /// Returns the error of `VerifierState::finish`.
+    pub fn finish(self) -> ProofResult<()> {
+        self.state.finish()
+    }
+}
@@ -19,3 +19,32 @@ pub fn pack_scalars_to_extension<F: Field, EF: ExtensionField<F>>(scalars: &[F])
         .map(|chunk| EF::from_basis_coefficients_slice(chunk).unwrap())
         .collect()
 }
+
+/// Number of bytes packed into each field element by [`bytes_to_field_elements`].
+pub const BYTES_PER_FIELD_ELEMENT: usize = 3;
+
+/// Number of elements encoding the length in [`bytes_to_field_elements`].
+const LENGTH_ELEMENTS: usize = u64::BITS.div_ceil(8 * BYTES_PER_FIELD_ELEMENT as u32) as usize;
+
+/// Canonical, injective encoding of bytes as field elements.
+///
+/// The length is encoded first, as a 64-bit integer split into `LENGTH_ELEMENTS` little-endian
+/// limbs of `BYTES_PER_FIELD_ELEMENT` bytes, followed by the bytes packed by chunks of
+/// `BYTES_PER_FIELD_ELEMENT` in little-endian order, the last chunk being zero-padded.
+/// Each element is smaller than 2^24, hence canonical in any field of at least 25 bits
+/// (in particular in KoalaBear, whose elements fit in 31 bits).
+pub fn bytes_to_field_elements<F: Field>(bytes: &[u8]) -> Vec<F> {
+    let limb_bits = 8 * BYTES_PER_FIELD_ELEMENT;
+    let len = bytes.len() as u64;
+    (0..LENGTH_ELEMENTS)
+        .map(|i| F::from_u64((len >> (i * limb_bits)) & ((1 << limb_bits) - 1)))
+        .chain(bytes.chunks(BYTES_PER_FIELD_ELEMENT).map(|chunk| {
+            F::from_u32(
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0u32, |acc, &byte| (acc << 8) | u32::from(byte)),
+            )
+        }))
+        .collect()
+}
@@ -1,36 +1,68 @@
-use std::collections::VecDeque;
-
 use crate::*;
 use p3_challenger::{FieldChallenger, GrindingChallenger};
 use p3_field::{BasedVectorSpace, ExtensionField, Field};
+use serde::Serialize;
 
 /// State held by the verifier in a Fiat-Shamir protocol.
 ///
 /// This struct reconstructs the transcript provided by the prover, consumes proof data,
 /// and manages a cryptographic challenger to derive challenges deterministically.
 #[derive(Debug)]
-pub struct VerifierState<F, EF, Challenger> {
+pub struct VerifierState<
+    F,
+    EF,
+    Challenger,
+    const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN,
+    const VECTOR_LEN: usize = LEAN_ISA_VECTOR_LEN,
+> {
     /// Cryptographic challenger used for sampling challenges and observing proof data.
     challenger: Challenger,
 
-    /// Indicates whether padding is used for alignment by LEAN_ISA_VECTOR_LEN (set to true for recursion)
+    /// Indicates whether padding is used for alignment by VECTOR_LEN (set to true for recursion)
     padding: bool,
 
+    /// Whether the proof was padded to a different vector length than `VECTOR_LEN`,
+    /// in which case every read and `finish` fail.
+    padding_mismatch: bool,
+
     /// Proof data buffer received from the prover, in base field elements.
     proof_data: Vec<F>,
 
-    merkle_hints: VecDeque<Vec<[F; 8]>>,
-
     /// Current read index into `proof_data`.
     index: usize,
 
+    /// Advice (hints) received from the prover, not observed by the challenger.
+    advice: Advice<F, DIGEST_LEN>,
+
+    /// Current read index into `advice.scalars`.
+    advice_index: usize,
+
+    /// Resource limits enforced on the proof and on each read.
+    limits: VerifierLimits,
+
+    /// Transcript schedule, recorded only after `record_layout` is called.
+    layout: Option<TranscriptLayout>,
+
+    /// Label attributed to the transcript operations currently performed (e.g. the protocol round).
+    label: Option<String>,
+
+    /// Cost of the transcript operations performed so far, by label.
+    counters: TranscriptCounters,
+
+    /// Whether all hints are bound to the transcript (see `set_hint_binding`).
+    bind_hints: bool,
+
+    /// Bound hints received since the last challenge, to be observed before the next one.
+    pending_bound_hints: Vec<F>,
+
     /// Marker to track the extension field type without storing it explicitly.
     _extension_field: std::marker::PhantomData<EF>,
 }
 
-impl<F, EF, Challenger> VerifierState<F, EF, Challenger>
+impl<F, EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize>
+    VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
 where
-    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F>,
+    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
     EF: ExtensionField<F>,
     F: Field,
 {
@@ -43,22 +75,196 @@ where
     ///
     /// # Returns
     /// A new `VerifierState` ready to consume proof data and derive challenges.
+    ///
+    /// If the proof was padded to a different vector length than `VECTOR_LEN`, every read
+    /// and `finish` return `ProofError::PaddingMismatch` (`try_new` reports it upfront).
     #[must_use]
-    pub fn new(proof: Proof<F>, challenger: Challenger) -> Self {
+    pub fn new(proof: Proof<F, DIGEST_LEN>, challenger: Challenger) -> Self {
+        const { assert!(VECTOR_LEN > 0, "VECTOR_LEN must be positive") };
         Self {
             challenger,
+            padding_mismatch: proof.padding && proof.vector_len != VECTOR_LEN,
             proof_data: proof.proof_data,
             index: 0,
             padding: proof.padding,
-            merkle_hints: proof.merkle_hints,
+            advice: proof.advice,
+            advice_index: 0,
+            limits: VerifierLimits::UNLIMITED,
+            layout: None,
+            label: None,
+            counters: TranscriptCounters::default(),
+            bind_hints: false,
+            pending_bound_hints: Vec::new(),
             _extension_field: std::marker::PhantomData,
         }
     }
 
+    /// Create a new verifier state, checking that the proof was padded to the same vector length
+    /// as this verifier expects.
+    ///
+    /// # Errors
+    /// Returns `ProofError::PaddingMismatch` if the proof was padded to a different vector length.
+    pub fn try_new(
+        proof: Proof<F, DIGEST_LEN>,
+        challenger: Challenger,
+    ) -> Result<Self, ProofError> {
+        let state = Self::new(proof, challenger);
+        state.check_padding()?;
+        Ok(state)
+    }
+
+    /// Fail if the proof was padded to a different vector length than `VECTOR_LEN`.
+    const fn check_padding(&self) -> ProofResult<()> {
+        if self.padding_mismatch {
+            return Err(ProofError::PaddingMismatch);
+        }
+        Ok(())
+    }
+
+    /// Create a new verifier state for an untrusted proof, enforcing resource limits.
+    ///
+    /// The shape of the proof is checked before any hashing, and each subsequent read
+    /// is checked against the same limits.
+    ///
+    /// # Errors
+    /// Returns `ProofError::LimitExceeded` if the proof exceeds `limits`,
+    /// or `ProofError::PaddingMismatch` if it was padded to a different vector length.
+    pub fn new_with_limits(
+        proof: Proof<F, DIGEST_LEN>,
+        challenger: Challenger,
+        limits: VerifierLimits,
+    ) -> Result<Self, ProofError> {
+        limits.check_proof(&proof)?;
+        let mut state = Self::try_new(proof, challenger)?;
+        state.limits = limits;
+        Ok(state)
+    }
+
+    /// Create a new verifier state bound to protocol `name` instantiated with `params`,
+    /// mirroring `ProverState::new_with_protocol`, and enforcing `limits` as `new_with_limits`
+    /// (use `VerifierLimits::UNLIMITED` for trusted proofs).
+    ///
+    /// # Errors
+    /// Returns `ProofError::LimitExceeded` if the proof exceeds `limits`,
+    /// `ProofError::ProtocolMismatch` if it was made for a different protocol or parameter set,
+    /// or `ProofError::PaddingMismatch` if it was padded to a different vector length.
+    pub fn new_with_protocol(
+        proof: Proof<F, DIGEST_LEN>,
+        challenger: Challenger,
+        name: &str,
+        params: &impl Serialize,
+        limits: VerifierLimits,
+    ) -> Result<Self, ProofError> {
+        Self::new_with_protocol_id(proof, challenger, ProtocolId::new(name, params), limits)
+    }
+
+    /// Create a new verifier state bound to `protocol_id`, as `new_with_protocol`.
+    ///
+    /// # Errors
+    /// Returns `ProofError::LimitExceeded` if the proof exceeds `limits`,
+    /// `ProofError::ProtocolMismatch` if it was made for a different protocol or parameter set,
+    /// or `ProofError::PaddingMismatch` if it was padded to a different vector length.
+    pub fn new_with_protocol_id(
+        proof: Proof<F, DIGEST_LEN>,
+        challenger: Challenger,
+        protocol_id: ProtocolId,
+        limits: VerifierLimits,
+    ) -> Result<Self, ProofError> {
+        if proof.protocol_id != Some(protocol_id) {
+            return Err(ProofError::ProtocolMismatch);
+        }
+        let mut state = Self::new_with_limits(proof, challenger, limits)?;
+        state.observe_bytes(&protocol_id.0);
+        Ok(state)
+    }
+
     pub const fn challenger(&self) -> &Challenger {
         &self.challenger
     }
 
+    /// Cost of the transcript operations performed so far, in total and by label.
+    pub const fn counters(&self) -> &TranscriptCounters {
+        &self.counters
+    }
+
+    /// Attribute the transcript operations performed from now on to `label` (e.g. a protocol round).
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = Some(label.into());
+    }
+
+    /// Stop attributing the transcript operations performed to a label.
+    pub fn clear_label(&mut self) {
+        self.label = None;
+    }
+
+    /// Attribute `counts` to the current label, along with the permutations performed
+    /// since the challenger reported `permutations_before`.
+    fn record_counts(&mut self, permutations_before: Option<usize>, mut counts: TranscriptCounts) {
+        if let (Some(before), Some(after)) = (permutations_before, self.challenger.n_permutations())
+        {
+            counts.permutations = after - before;
+        }
+        self.counters.record(self.label.as_deref(), counts);
+    }
+
+    /// Bind all subsequent hints to the transcript, mirroring `ProverState::set_hint_binding`.
+    ///
+    /// Hints received after the last challenge are never bound.
+    pub fn set_hint_binding(&mut self, enabled: bool) {
+        self.bind_hints = enabled;
+    }
+
+    /// Observe the bound hints received since the last challenge, prefixed by their number.
+    fn bind_pending_hints(&mut self) {
+        if self.pending_bound_hints.is_empty() {
+            return;
+        }
+        let hints = std::mem::take(&mut self.pending_bound_hints);
+        self.record_op(TranscriptOp::BindHints { len: hints.len() });
+        let permutations = self.challenger.n_permutations();
+        self.challenger.observe_slice(&[F::from_usize(hints.len())]);
+        self.challenger.observe_slice(&hints);
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                observed: 1 + hints.len(),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Start recording the transcript schedule followed by this verifier.
+    ///
+    /// Any previously recorded layout is discarded.
+    pub fn record_layout(&mut self) {
+        self.layout = Some(TranscriptLayout::new(self.padding, VECTOR_LEN));
+    }
+
+    /// Stop recording and return the transcript schedule recorded since `record_layout`.
+    pub fn take_layout(&mut self) -> Option<TranscriptLayout> {
+        self.layout.take()
+    }
+
+    /// Announce `op` to the challenger and record it in the layout, if recording.
+    ///
+    /// Called before the challenger is used for `op`.
+    fn record_op(&mut self, op: TranscriptOp) {
+        self.challenger
+            .set_transcript_op(&op, self.label.as_deref());
+        if let Some(layout) = &mut self.layout {
+            layout.push(op);
+        }
+    }
+
+    /// Number of zero lanes following an item of `len` elements in padding mode.
+    const fn padded_lanes(&self, len: usize) -> usize {
+        if self.padding {
+            len.next_multiple_of(VECTOR_LEN) - len
+        } else {
+            0
+        }
+    }
+
     /// Consume and return `n` base scalars from the proof data, observing them in the challenger.
     ///
     /// # Arguments
@@ -67,6 +273,20 @@ where
     /// # Errors
     /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
     pub fn next_base_scalars_vec(&mut self, n: usize) -> Result<Vec<F>, ProofError> {
+        self.record_op(TranscriptOp::Observe {
+            offset: self.index,
+            count: n,
+            item_len: 1,
+            padded_lanes: 0,
+        });
+        self.read_observed(n)
+    }
+
+    /// Consume `n` base scalars from the proof data and observe them, without recording them.
+    fn read_observed(&mut self, n: usize) -> Result<Vec<F>, ProofError> {
+        self.check_padding()?;
+        self.limits.check_read(n)?;
+
         // Check that enough data remains to read `n` elements.
         if n > self.proof_data.len() - self.index {
             return Err(ProofError::ExceededTranscript);
@@ -77,7 +297,15 @@ where
         self.index += n;
 
         // Observe these scalars in the challenger to update its state.
+        let permutations = self.challenger.n_permutations();
         self.challenger.observe_slice(&scalars);
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                observed: n,
+                ..Default::default()
+            },
+        );
 
         Ok(scalars)
     }
@@ -99,20 +327,51 @@ where
     /// # Errors
     /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
     pub fn next_extension_scalars_vec(&mut self, n: usize) -> Result<Vec<EF>, ProofError> {
+        self.next_scalars_in(n)
+    }
+
+    /// Consume and return `n` scalars of any extension field `E` of `F` (not necessarily `EF`),
+    /// observing them in the challenger. Mirrors `ProverState::add_scalars_in`.
+    ///
+    /// # Arguments
+    /// - `n`: Number of `E` scalars to read.
+    ///
+    /// # Errors
+    /// Returns `ProofError::LimitExceeded` if the `n` scalars span more elements than a single
+    /// read allows, `ProofError::ExceededTranscript` if insufficient data remains,
+    /// or `ProofError::InvalidProof` if the padding lanes are not zero.
+    pub fn next_scalars_in<E: ExtensionField<F>>(
+        &mut self,
+        n: usize,
+    ) -> Result<Vec<E>, ProofError> {
         // Calculate number of base scalars per extension scalar.
-        let extension_size = <EF as BasedVectorSpace<F>>::DIMENSION;
+        let extension_size = <E as BasedVectorSpace<F>>::DIMENSION;
+        let padded_lanes = self.padded_lanes(extension_size);
+        self.limits.check_read(
+            n.checked_mul(extension_size + padded_lanes)
+                .ok_or(ProofError::LimitExceeded)?,
+        )?;
+
+        self.record_op(TranscriptOp::Observe {
+            offset: self.index,
+            count: n,
+            item_len: extension_size,
+            padded_lanes,
+        });
 
         let mut res = Vec::new();
         for _ in 0..n {
             if self.padding {
-                let base_scalars = self.next_base_scalars_const::<LEAN_ISA_VECTOR_LEN>()?;
-                assert!(base_scalars[extension_size..].iter().all(|&x| x == F::ZERO));
+                let base_scalars = self.read_observed(extension_size + padded_lanes)?;
+                if base_scalars[extension_size..].iter().any(|&x| x != F::ZERO) {
+                    return Err(ProofError::InvalidProof);
+                }
                 res.push(
-                    EF::from_basis_coefficients_slice(&base_scalars[..extension_size]).unwrap(),
+                    E::from_basis_coefficients_slice(&base_scalars[..extension_size]).unwrap(),
                 );
             } else {
-                let base_scalars = self.next_base_scalars_vec(extension_size)?;
-                res.push(EF::from_basis_coefficients_slice(&base_scalars).unwrap());
+                let base_scalars = self.read_observed(extension_size)?;
+                res.push(E::from_basis_coefficients_slice(&base_scalars).unwrap());
             }
         }
         Ok(res)
@@ -134,6 +393,93 @@ where
         Ok(self.next_extension_scalars_vec(1)?[0])
     }
 
+    /// Consume a Merkle commitment root from the proof data, observing it in the challenger.
+    ///
+    /// # Errors
+    /// Returns `ProofError::ExceededTranscript` if insufficient data remains,
+    /// or `ProofError::InvalidProof` if the padding lanes are not zero.
+    pub fn next_commitment(&mut self) -> Result<Digest<F, DIGEST_LEN>, ProofError> {
+        let padded_lanes = self.padded_lanes(DIGEST_LEN);
+        self.record_op(TranscriptOp::Observe {
+            offset: self.index,
+            count: 1,
+            item_len: DIGEST_LEN,
+            padded_lanes,
+        });
+        let scalars = self.read_observed(DIGEST_LEN + padded_lanes)?;
+        if scalars[DIGEST_LEN..].iter().any(|&x| x != F::ZERO) {
+            return Err(ProofError::InvalidProof);
+        }
+        Ok(Digest(scalars[..DIGEST_LEN].try_into().unwrap()))
+    }
+
+    /// Observe public base field scalars (e.g. the statement) in the challenger.
+    ///
+    /// Mirrors `ProverState::observe_public_base`: nothing is read from the proof.
+    ///
+    /// # Arguments
+    /// - `scalars`: Slice of public base field elements to observe.
+    pub fn observe_public_base(&mut self, scalars: &[F]) {
+        self.record_op(TranscriptOp::ObservePublic {
+            count: scalars.len(),
+            item_len: 1,
+            padded_lanes: 0,
+        });
+        let permutations = self.challenger.n_permutations();
+        self.challenger.observe_slice(scalars);
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                observed: scalars.len(),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Observe public bytes (e.g. a protocol name, a circuit hash or a message) in the challenger,
+    /// without reading from the proof.
+    ///
+    /// The bytes are encoded with `bytes_to_field_elements`, which is length-prefixed, so that
+    /// consecutive calls cannot be confused with each other.
+    pub fn observe_bytes(&mut self, bytes: &[u8]) {
+        self.observe_public_base(&bytes_to_field_elements(bytes));
+    }
+
+    /// Observe a public label in the challenger, encoded as its UTF-8 bytes (see `observe_bytes`).
+    pub fn observe_label(&mut self, label: &str) {
+        self.observe_bytes(label.as_bytes());
+    }
+
+    /// Observe public extension field scalars in the challenger, without reading from the proof.
+    ///
+    /// In padding mode, each scalar is padded with zeros to a multiple of `VECTOR_LEN`,
+    /// as in `next_extension_scalars_vec`.
+    ///
+    /// # Arguments
+    /// - `scalars`: Slice of public extension field elements to observe.
+    pub fn observe_public_extension(&mut self, scalars: &[EF]) {
+        let extension_size = <EF as BasedVectorSpace<F>>::DIMENSION;
+        let padded_lanes = self.padded_lanes(extension_size);
+        self.record_op(TranscriptOp::ObservePublic {
+            count: scalars.len(),
+            item_len: extension_size,
+            padded_lanes,
+        });
+        let permutations = self.challenger.n_permutations();
+        for ef in scalars {
+            let mut base_scalars = ef.as_basis_coefficients_slice().to_vec();
+            base_scalars.resize(extension_size + padded_lanes, F::ZERO);
+            self.challenger.observe_slice(&base_scalars);
+        }
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                observed: scalars.len() * (extension_size + padded_lanes),
+                ..Default::default()
+            },
+        );
+    }
+
     /// Consume and return `n` base scalars as hints (not observed by the challenger).
     ///
     /// # Arguments
@@ -142,22 +488,61 @@ where
     /// # Errors
     /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
     pub fn receive_hint_base_scalars(&mut self, n: usize) -> Result<Vec<F>, ProofError> {
-        // Check that enough data remains to read `n` elements.
-        if n > self.proof_data.len() - self.index {
+        self.read_hint_scalars(n, self.bind_hints)
+    }
+
+    /// Consume and return `n` base scalars as bound hints, observed right before the next
+    /// challenge is sampled (mirrors `ProverState::bound_hint_base_scalars`).
+    ///
+    /// # Errors
+    /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
+    pub fn receive_bound_hint_base_scalars(&mut self, n: usize) -> Result<Vec<F>, ProofError> {
+        self.read_hint_scalars(n, true)
+    }
+
+    fn read_hint_scalars(&mut self, n: usize, bound: bool) -> Result<Vec<F>, ProofError> {
+        self.check_padding()?;
+        self.limits.check_advice_read(n)?;
+
+        // Check that enough advice remains to read `n` elements.
+        if n > self.advice.scalars.len() - self.advice_index {
             return Err(ProofError::ExceededTranscript);
         }
 
         // Slice out the next `n` scalars and copy them.
-        let scalars = self.proof_data[self.index..self.index + n].to_vec();
-        self.index += n;
+        let scalars = self.advice.scalars[self.advice_index..self.advice_index + n].to_vec();
+        self.record_op(TranscriptOp::Hint {
+            offset: self.advice_index,
+            len: n,
+        });
+        self.advice_index += n;
+
+        if bound {
+            self.pending_bound_hints.extend(&scalars);
+        }
 
         Ok(scalars)
     }
 
-    pub fn receive_hint_merkle_path(&mut self) -> Result<Vec<[F; 8]>, ProofError> {
-        self.merkle_hints
+    pub fn receive_hint_merkle_path(&mut self) -> Result<Vec<Digest<F, DIGEST_LEN>>, ProofError> {
+        self.check_padding()?;
+        let path = self
+            .advice
+            .merkle_paths
             .pop_front()
-            .ok_or(ProofError::ExceededTranscript)
+            .ok_or(ProofError::ExceededTranscript)?;
+        if path.len() > self.limits.max_merkle_path_depth {
+            return Err(ProofError::LimitExceeded);
+        }
+        self.record_op(TranscriptOp::MerklePath {
+            depth: path.len(),
+            digest_len: DIGEST_LEN,
+        });
+        if self.bind_hints {
+            self.pending_bound_hints
+                .extend(path.iter().flat_map(|node| node.0));
+        }
+        Ok(path)
     }
 
     /// Consume and return `n` extension scalars as hints (not observed by the challenger).
@@ -168,11 +553,34 @@ where
     /// # Errors
     /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
     pub fn receive_hint_extension_scalars(&mut self, n: usize) -> Result<Vec<EF>, ProofError> {
+        self.read_hint_extension_scalars(n, self.bind_hints)
+    }
+
+    /// Consume and return `n` extension scalars as bound hints, observed right before the next
+    /// challenge is sampled (mirrors `ProverState::bound_hint_extension_scalars`).
+    ///
+    /// # Errors
+    /// Returns `ProofError::ExceededTranscript` if insufficient data remains.
+    pub fn receive_bound_hint_extension_scalars(
+        &mut self,
+        n: usize,
+    ) -> Result<Vec<EF>, ProofError> {
+        self.read_hint_extension_scalars(n, true)
+    }
+
+    fn read_hint_extension_scalars(
+        &mut self,
+        n: usize,
+        bound: bool,
+    ) -> Result<Vec<EF>, ProofError> {
         let extension_size = <EF as BasedVectorSpace<F>>::DIMENSION;
+        let n_base_scalars = n
+            .checked_mul(extension_size)
+            .ok_or(ProofError::LimitExceeded)?;
 
         // Read and pack into extension elements without challenger observation.
         Ok(pack_scalars_to_extension(
-            &self.receive_hint_base_scalars(n * extension_size)?,
+            &self.read_hint_scalars(n_base_scalars, bound)?,
         ))
     }
 
@@ -181,7 +589,29 @@ where
     /// # Returns
     /// A new challenge element in the extension field.
     pub fn sample(&mut self) -> EF {
-        self.challenger.sample_algebra_element()
+        self.sample_in()
+    }
+
+    /// Sample a new random element of any extension field `E` of `F` using the challenger.
+    ///
+    /// # Returns
+    /// A new challenge element in `E`.
+    pub fn sample_in<E: ExtensionField<F>>(&mut self) -> E {
+        self.bind_pending_hints();
+        self.record_op(TranscriptOp::Sample {
+            count: 1,
+            item_len: <E as BasedVectorSpace<F>>::DIMENSION,
+        });
+        let permutations = self.challenger.n_permutations();
+        let challenge = self.challenger.sample_algebra_element();
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                squeezed: <E as BasedVectorSpace<F>>::DIMENSION,
+                ..Default::default()
+            },
+        );
+        challenge
     }
 
     pub fn sample_vec(&mut self, len: usize) -> Vec<EF> {
@@ -196,7 +626,18 @@ where
     /// # Returns
     /// A uniformly random value with `bits` bits.
     pub fn sample_bits(&mut self, bits: usize) -> usize {
-        self.challenger.sample_bits(bits)
+        self.bind_pending_hints();
+        self.record_op(TranscriptOp::SampleBits { bits });
+        let permutations = self.challenger.n_permutations();
+        let res = self.challenger.sample_bits(bits);
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                squeezed: 1,
+                ..Default::default()
+            },
+        );
+        res
     }
 
     /// Verify PoW grinding witness correctness.
@@ -206,35 +647,84 @@ where
     ///
     /// # Errors
     /// Returns `ProofError::ExceededTranscript` if no data remains,
+    /// `ProofError::InvalidProof` if the padding lanes of the witness are not zero,
     /// or `ProofError::InvalidGrindingWitness` if the witness does not satisfy the difficulty.
     pub fn check_pow_grinding(&mut self, bits: usize) -> Result<(), ProofError> {
         // If no grinding is required, succeed immediately.
         if bits == 0 {
             return Ok(());
         }
+        self.check_padding()?;
+
+        self.bind_pending_hints();
 
         // Ensure there is enough of witness elements to consume.
-        if self.index + if self.padding { LEAN_ISA_VECTOR_LEN } else { 1 } > self.proof_data.len() {
+        if self.index + if self.padding { VECTOR_LEN } else { 1 } > self.proof_data.len() {
             return Err(ProofError::ExceededTranscript);
         }
 
         let witness = self.proof_data[self.index];
-        self.index += if self.padding { LEAN_ISA_VECTOR_LEN } else { 1 };
+        // The padding lanes of the witness are not observed, so they must be checked instead.
+        let padded_lanes = self.padded_lanes(1);
+        if self.proof_data[self.index + 1..self.index + 1 + padded_lanes]
+            .iter()
+            .any(|&x| x != F::ZERO)
+        {
+            return Err(ProofError::InvalidProof);
+        }
+        self.record_op(TranscriptOp::Grind {
+            offset: self.index,
+            bits,
+            padded_lanes,
+        });
+        self.index += if self.padding { VECTOR_LEN } else { 1 };
 
         // Verify the witness using the challenger.
-        if self.challenger.check_witness(bits, witness) {
+        let permutations = self.challenger.n_permutations();
+        let valid = self.challenger.check_witness(bits, witness);
+        self.record_counts(
+            permutations,
+            TranscriptCounts {
+                observed: 1,
+                squeezed: 1,
+                grinding_attempts: 1,
+                ..Default::default()
+            },
+        );
+        if valid {
             Ok(())
         } else {
             Err(ProofError::InvalidGrindingWitness)
         }
     }
+
+    /// End the verification, checking that the whole proof was consumed.
+    ///
+    /// Without this check, a proof with trailing proof data or unused hints appended to an
+    /// honest proof would be accepted.
+    ///
+    /// # Errors
+    /// Returns `ProofError::InvalidProof` if some proof data, advice scalars or merkle paths
+    /// were not read, or `ProofError::PaddingMismatch` if the proof was padded to a different
+    /// vector length.
+    pub fn finish(self) -> ProofResult<()> {
+        self.check_padding()?;
+        if self.index != self.proof_data.len()
+            || self.advice_index != self.advice.scalars.len()
+            || !self.advice.merkle_paths.is_empty()
+        {
+            return Err(ProofError::InvalidProof);
+        }
+        Ok(())
+    }
 }
 
-impl<F, EF, Challenger> ChallengeSampler<EF> for VerifierState<F, EF, Challenger>
+impl<F, EF, Challenger, const DIGEST_LEN: usize, const VECTOR_LEN: usize> ChallengeSampler<EF>
+    for VerifierState<F, EF, Challenger, DIGEST_LEN, VECTOR_LEN>
 where
     EF: ExtensionField<F>,
     F: Field,
-    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F>,
+    Challenger: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
 {
     fn sample_bits(&mut self, bits: usize) -> usize {
         self.sample_bits(bits)
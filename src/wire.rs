@@ -0,0 +1,135 @@
+use p3_field::Field;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Wire encoding of a [`Proof`], without the padding zeros of `proof_data`.
+///
+/// In padding mode, `proof_data` is split into chunks of `vector_len` elements, and only the
+/// elements preceding the trailing zeros of each chunk are sent, at the cost of one byte per
+/// chunk. The trailing zeros are restored on decoding, whether they are padding or not.
+///
+/// The padding zeros of an item are stripped only when the item ends on a chunk boundary.
+/// Base scalars are not padded, so the items following them may be misaligned, in which case
+/// their padding zeros are sent. The advice is sent as is (hints are never padded).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WireProof<F, const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN> {
+    pub padding: bool,
+    pub vector_len: usize,
+    pub proof_size: usize,
+    /// Length of the padded `proof_data`.
+    pub proof_data_len: usize,
+    /// Number of elements kept in each chunk of `proof_data` (empty without padding).
+    pub chunk_lens: Vec<u8>,
+    /// Kept elements of `proof_data`, chunk after chunk.
+    pub proof_data: Vec<F>,
+    pub advice: Advice<F, DIGEST_LEN>,
+    pub protocol_id: Option<ProtocolId>,
+    pub config: ProofConfig,
+}
+
+impl<F: Field, const DIGEST_LEN: usize> Proof<F, DIGEST_LEN> {
+    /// Encode the proof for the wire, stripping the padding zeros of `proof_data`.
+    ///
+    /// # Panics
+    /// Panics in padding mode if `vector_len` is zero or does not fit in a byte.
+    #[must_use]
+    pub fn to_wire(&self) -> WireProof<F, DIGEST_LEN> {
+        let mut chunk_lens = Vec::new();
+        let mut proof_data = Vec::new();
+        if self.padding {
+            assert!(self.vector_len > 0 && self.vector_len <= u8::MAX as usize);
+            for chunk in self.proof_data.chunks(self.vector_len) {
+                let kept = chunk
+                    .iter()
+                    .rposition(|x| !x.is_zero())
+                    .map_or(0, |i| i + 1);
+                chunk_lens.push(kept as u8);
+                proof_data.extend(&chunk[..kept]);
+            }
+        } else {
+            proof_data.clone_from(&self.proof_data);
+        }
+
+        WireProof {
+            padding: self.padding,
+            vector_len: self.vector_len,
+            proof_size: self.proof_size,
+            proof_data_len: self.proof_data.len(),
+            chunk_lens,
+            proof_data,
+            advice: self.advice.clone(),
+            protocol_id: self.protocol_id,
+            config: self.config,
+        }
+    }
+}
+
+impl<F: Field, const DIGEST_LEN: usize> WireProof<F, DIGEST_LEN> {
+    /// Decode the proof, reconstituting the padded `proof_data` layout expected by
+    /// `VerifierState`.
+    ///
+    /// # Arguments
+    /// - `limits`: Resource limits, checked before allocating the padded `proof_data`.
+    ///
+    /// # Errors
+    /// Returns `ProofError::LimitExceeded` if the decoded proof exceeds `limits`, and
+    /// `ProofError::InvalidProof` if the encoding is malformed.
+    pub fn into_proof(self, limits: &VerifierLimits) -> ProofResult<Proof<F, DIGEST_LEN>> {
+        limits.check_read(self.proof_data_len)?;
+
+        let proof_data = if self.padding {
+            self.unpad_proof_data()?
+        } else {
+            if !self.chunk_lens.is_empty() || self.proof_data.len() != self.proof_data_len {
+                return Err(ProofError::InvalidProof);
+            }
+            self.proof_data
+        };
+
+        let proof = Proof {
+            proof_data,
+            padding: self.padding,
+            vector_len: self.vector_len,
+            proof_size: self.proof_size,
+            advice: self.advice,
+            protocol_id: self.protocol_id,
+            config: self.config,
+        };
+        limits.check_proof(&proof)?;
+        Ok(proof)
+    }
+
+    fn unpad_proof_data(&self) -> ProofResult<Vec<F>> {
+        if self.vector_len == 0
+            || self.chunk_lens.len() != self.proof_data_len.div_ceil(self.vector_len)
+        {
+            return Err(ProofError::InvalidProof);
+        }
+
+        let mut proof_data = Vec::with_capacity(self.proof_data_len);
+        let mut index = 0;
+        for (i, &kept) in self.chunk_lens.iter().enumerate() {
+            let chunk_len = self
+                .vector_len
+                .min(self.proof_data_len - i * self.vector_len);
+            let kept = kept as usize;
+            if kept > chunk_len || index + kept > self.proof_data.len() {
+                return Err(ProofError::InvalidProof);
+            }
+            let kept_data = &self.proof_data[index..index + kept];
+            // Reject non-canonical encodings, so that each proof has a single wire encoding.
+            if kept_data.last().is_some_and(Field::is_zero) {
+                return Err(ProofError::InvalidProof);
+            }
+            proof_data.extend(kept_data);
+            proof_data.resize(proof_data.len() + chunk_len - kept, F::ZERO);
+            index += kept;
+        }
+
+        if index != self.proof_data.len() {
+            return Err(ProofError::InvalidProof);
+        }
+        Ok(proof_data)
+    }
+}
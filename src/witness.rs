@@ -0,0 +1,267 @@
+use std::sync::{Arc, Mutex};
+
+use p3_challenger::{
+    CanObserve, CanSample, CanSampleBits, DuplexChallenger, FieldChallenger, GrindingChallenger,
+};
+use p3_field::Field;
+use p3_koala_bear::{KoalaBear, Poseidon2KoalaBear};
+use p3_symmetric::{CryptographicPermutation, Permutation};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Challenger operation during which a permutation was performed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChallengerOp {
+    #[default]
+    Observe,
+    Sample,
+    SampleBits,
+    CheckWitness,
+}
+
+/// Input and output states of a single permutation call.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PermutationRecord<F> {
+    /// Challenger operation that triggered the permutation.
+    pub op: ChallengerOp,
+    /// Transcript operation of the `VerifierState` that triggered the permutation, if any.
+    pub transcript_op: Option<TranscriptOp>,
+    /// Label under which `transcript_op` was performed (see `VerifierState::set_label`).
+    pub label: Option<String>,
+    pub input: Vec<F>,
+    pub output: Vec<F>,
+}
+
+impl<F: Copy> PermutationRecord<F> {
+    /// Row of the witness table: input state followed by output state.
+    pub fn to_row(&self) -> Vec<F> {
+        self.input.iter().chain(&self.output).copied().collect()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct PermutationTrace<F> {
+    op: ChallengerOp,
+    transcript_op: Option<TranscriptOp>,
+    label: Option<String>,
+    paused: bool,
+    n_permutations: usize,
+    records: Vec<PermutationRecord<F>>,
+}
+
+/// A permutation that logs each call into a trace shared with its [`PermutationRecorder`].
+#[derive(Clone, Debug)]
+pub struct RecordingPermutation<F, P> {
+    inner: P,
+    trace: Arc<Mutex<PermutationTrace<F>>>,
+}
+
+impl<F: Field, P: Permutation<[F; WIDTH]>, const WIDTH: usize> Permutation<[F; WIDTH]>
+    for RecordingPermutation<F, P>
+{
+    fn permute_mut(&self, state: &mut [F; WIDTH]) {
+        let input = *state;
+        self.inner.permute_mut(state);
+
+        let mut trace = self.trace.lock().unwrap();
+        trace.n_permutations += 1;
+        if !trace.paused {
+            let record = PermutationRecord {
+                op: trace.op,
+                transcript_op: trace.transcript_op.clone(),
+                label: trace.label.clone(),
+                input: input.to_vec(),
+                output: state.to_vec(),
+            };
+            trace.records.push(record);
+        }
+    }
+}
+
+impl<F: Field, P: CryptographicPermutation<[F; WIDTH]>, const WIDTH: usize>
+    CryptographicPermutation<[F; WIDTH]> for RecordingPermutation<F, P>
+{
+}
+
+/// A duplex challenger recording the input and output states of every permutation it performs,
+/// together with the challenger operation that triggered it.
+///
+/// Used as the challenger of a `VerifierState`, it exports the permutation witness table of a
+/// native verification run, which a recursive prover can reuse instead of re-deriving the
+/// sponge states. Each record is also tagged with the transcript operation and label of the
+/// verifier that triggered it. The permutations performed while searching for a grinding witness
+/// are not recorded (only the final check is).
+///
+/// A clone starts with a copy of the records made so far, and records independently afterwards.
+#[derive(Debug)]
+pub struct PermutationRecorder<F, P, const WIDTH: usize, const RATE: usize>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    inner: DuplexChallenger<F, RecordingPermutation<F, P>, WIDTH, RATE>,
+    trace: Arc<Mutex<PermutationTrace<F>>>,
+}
+
+pub type KoalaBearPermutationRecorder =
+    PermutationRecorder<KoalaBear, Poseidon2KoalaBear<16>, 16, 8>;
+
+impl<F, P, const WIDTH: usize, const RATE: usize> PermutationRecorder<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    #[must_use]
+    pub fn new(permutation: P) -> Self {
+        let trace = Arc::new(Mutex::new(PermutationTrace::default()));
+        Self {
+            inner: DuplexChallenger::new(RecordingPermutation {
+                inner: permutation,
+                trace: trace.clone(),
+            }),
+            trace,
+        }
+    }
+
+    /// The permutations recorded so far, in execution order.
+    pub fn permutation_records(&self) -> Vec<PermutationRecord<F>> {
+        self.trace.lock().unwrap().records.clone()
+    }
+
+    /// The recorded permutations as a witness table, one row (input then output state) per call.
+    pub fn witness_table(&self) -> Vec<Vec<F>> {
+        self.trace
+            .lock()
+            .unwrap()
+            .records
+            .iter()
+            .map(PermutationRecord::to_row)
+            .collect()
+    }
+
+    fn with_op<R>(
+        &mut self,
+        op: ChallengerOp,
+        f: impl FnOnce(&mut DuplexChallenger<F, RecordingPermutation<F, P>, WIDTH, RATE>) -> R,
+    ) -> R {
+        self.trace.lock().unwrap().op = op;
+        f(&mut self.inner)
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> Clone for PermutationRecorder<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    fn clone(&self) -> Self {
+        // The derived clone would share the trace between the clones.
+        let trace = Arc::new(Mutex::new(self.trace.lock().unwrap().clone()));
+        Self {
+            inner: DuplexChallenger {
+                sponge_state: self.inner.sponge_state,
+                input_buffer: self.inner.input_buffer.clone(),
+                output_buffer: self.inner.output_buffer.clone(),
+                permutation: RecordingPermutation {
+                    inner: self.inner.permutation.inner.clone(),
+                    trace: trace.clone(),
+                },
+            },
+            trace,
+        }
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> CanObserve<F>
+    for PermutationRecorder<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+    DuplexChallenger<F, RecordingPermutation<F, P>, WIDTH, RATE>: FieldChallenger<F>,
+{
+    fn observe(&mut self, value: F) {
+        self.with_op(ChallengerOp::Observe, |c| c.observe(value));
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> CanSample<F>
+    for PermutationRecorder<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+    DuplexChallenger<F, RecordingPermutation<F, P>, WIDTH, RATE>: FieldChallenger<F>,
+{
+    fn sample(&mut self) -> F {
+        self.with_op(ChallengerOp::Sample, |c| c.sample())
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> CanSampleBits<usize>
+    for PermutationRecorder<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+    DuplexChallenger<F, RecordingPermutation<F, P>, WIDTH, RATE>: FieldChallenger<F>,
+{
+    fn sample_bits(&mut self, bits: usize) -> usize {
+        self.with_op(ChallengerOp::SampleBits, |c| c.sample_bits(bits))
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> FieldChallenger<F>
+    for PermutationRecorder<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+    DuplexChallenger<F, RecordingPermutation<F, P>, WIDTH, RATE>: FieldChallenger<F>,
+{
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> GrindingChallenger
+    for PermutationRecorder<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+    DuplexChallenger<F, RecordingPermutation<F, P>, WIDTH, RATE>:
+        FieldChallenger<F> + GrindingChallenger<Witness = F>,
+{
+    type Witness = F;
+
+    fn grind(&mut self, bits: usize) -> F {
+        // Search on a copy with recording paused, then replay the successful check on `self`.
+        self.trace.lock().unwrap().paused = true;
+        let witness = self.inner.clone().grind(bits);
+        self.trace.lock().unwrap().paused = false;
+
+        assert!(self.check_witness(bits, witness));
+        witness
+    }
+
+    fn check_witness(&mut self, bits: usize, witness: F) -> bool {
+        self.with_op(ChallengerOp::CheckWitness, |c| {
+            c.check_witness(bits, witness)
+        })
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> ChallengerState
+    for PermutationRecorder<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    fn state(&self) -> String {
+        format!("{:?}", self.inner.sponge_state)
+    }
+
+    fn n_permutations(&self) -> Option<usize> {
+        Some(self.trace.lock().unwrap().n_permutations)
+    }
+
+    fn set_transcript_op(&mut self, op: &TranscriptOp, label: Option<&str>) {
+        let mut trace = self.trace.lock().unwrap();
+        trace.transcript_op = Some(op.clone());
+        trace.label = label.map(str::to_string);
+    }
+}
@@ -7,5 +7,15 @@ pub type FPacking<F> = <F as Field>::Packing;
 pub type PFPacking<F> = <PF<F> as Field>::Packing;
 pub type EFPacking<EF> = <EF as ExtensionField<PF<EF>>>::ExtensionPacking;
 
-pub type FSProver<EF, Challenger> = ProverState<PF<EF>, EF, Challenger>;
-pub type FSVerifier<EF, Challenger> = VerifierState<PF<EF>, EF, Challenger>;
+pub type FSProver<
+    EF,
+    Challenger,
+    const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN,
+    const VECTOR_LEN: usize = LEAN_ISA_VECTOR_LEN,
+> = ProverState<PF<EF>, EF, Challenger, DIGEST_LEN, VECTOR_LEN>;
+pub type FSVerifier<
+    EF,
+    Challenger,
+    const DIGEST_LEN: usize = DEFAULT_DIGEST_LEN,
+    const VECTOR_LEN: usize = LEAN_ISA_VECTOR_LEN,
+> = VerifierState<PF<EF>, EF, Challenger, DIGEST_LEN, VECTOR_LEN>;
@@ -0,0 +1,177 @@
+//! Verifier state selected at runtime from the proof's configuration header.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_challenger::{FieldChallenger, GrindingChallenger};
+use p3_field::{ExtensionField, PrimeCharacteristicRing, extension::BinomialExtensionField};
+
+type EF8 = BinomialExtensionField<F, 8>;
+
+const NAME: &str = "any-verifier-test";
+const PARAMS: (usize, usize) = (4, 128);
+const PUBLIC_KEY: &[u8] = b"public key";
+const MESSAGE: &[u8] = b"message";
+
+/// Sends three scalars, then echoes the challenge they yield.
+fn respond<E, C>(prover: &mut ProverState<F, E, C>)
+where
+    E: ExtensionField<F>,
+    C: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
+{
+    prover.add_base_scalars(&[F::ONE, F::TWO, F::from_u32(3)]);
+    let challenge = prover.sample();
+    prover.add_extension_scalar(challenge);
+}
+
+fn prove<E, C>(challenger: C) -> Proof<F>
+where
+    E: ExtensionField<F>,
+    C: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState + Clone,
+{
+    let mut prover = ProverState::<F, E, _>::new_with_protocol(challenger, false, NAME, &PARAMS);
+    respond(&mut prover);
+    prover.into_proof()
+}
+
+/// Verifier side of [`respond`].
+struct Echo;
+
+impl VerifierVisitor for Echo {
+    type Output = ProofResult<()>;
+
+    fn visit<E, C>(self, verifier: &mut VerifierState<F, E, C>) -> ProofResult<()>
+    where
+        E: ExtensionField<F> + PrimeCharacteristicRing<PrimeSubfield = F>,
+        C: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
+    {
+        verifier.next_base_scalars_vec(3)?;
+        let challenge = verifier.sample();
+        if verifier.next_extension_scalar()? != challenge {
+            return Err(ProofError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+const ALL: [(ChallengerKind, usize); 10] = [
+    (ChallengerKind::Poseidon2KoalaBear, 4),
+    (ChallengerKind::Poseidon2KoalaBear, 8),
+    (ChallengerKind::KeccakKoalaBear, 4),
+    (ChallengerKind::KeccakKoalaBear, 8),
+    (ChallengerKind::Sha256KoalaBear, 4),
+    (ChallengerKind::Sha256KoalaBear, 8),
+    (ChallengerKind::Blake3KoalaBear, 4),
+    (ChallengerKind::Blake3KoalaBear, 8),
+    (ChallengerKind::EvmKoalaBear, 4),
+    (ChallengerKind::EvmKoalaBear, 8),
+];
+
+fn protocol(proof: Proof<F>) -> TranscriptBinding<'static> {
+    TranscriptBinding::Protocol {
+        proof,
+        protocol_id: ProtocolId::new(NAME, &PARAMS),
+    }
+}
+
+fn verify(binding: TranscriptBinding<'_>, allowed: &[(ChallengerKind, usize)]) -> ProofResult<()> {
+    let mut verifier = AnyVerifierState::new(binding, allowed, VerifierLimits::UNLIMITED)?;
+    verifier.visit(Echo)?;
+    verifier.finish()
+}
+
+#[test]
+fn every_challenger_kind_verifies() {
+    let proofs = [
+        prove::<EF, _>(challenger()),
+        prove::<EF8, _>(challenger()),
+        prove::<EF, _>(keccak_challenger()),
+        prove::<EF8, _>(keccak_challenger()),
+        prove::<EF, _>(sha256_challenger()),
+        prove::<EF8, _>(sha256_challenger()),
+        prove::<EF, _>(blake3_challenger()),
+        prove::<EF8, _>(blake3_challenger()),
+        prove::<EF, _>(KoalaBearEvmChallenger::new()),
+        prove::<EF8, _>(KoalaBearEvmChallenger::new()),
+    ];
+    for (proof, config) in proofs.into_iter().zip(ALL) {
+        assert_eq!(
+            (proof.config.challenger, proof.config.extension_degree),
+            (Some(config.0), config.1)
+        );
+        verify(protocol(proof), &ALL).unwrap();
+    }
+}
+
+#[test]
+fn configurations_outside_the_allow_list_are_rejected() {
+    let proof = prove::<EF8, _>(keccak_challenger());
+    let allowed = [
+        (ChallengerKind::KeccakKoalaBear, 4),
+        (ChallengerKind::Poseidon2KoalaBear, 8),
+    ];
+    assert!(matches!(
+        verify(protocol(proof.clone()), &allowed),
+        Err(ProofError::UnsupportedConfig)
+    ));
+
+    // A proof without a configuration header is never accepted.
+    let mut unknown = proof;
+    unknown.config.challenger = None;
+    assert!(matches!(
+        verify(protocol(unknown), &ALL),
+        Err(ProofError::UnsupportedConfig)
+    ));
+}
+
+#[test]
+fn other_protocols_are_rejected() {
+    let proof = prove::<EF, _>(challenger());
+    let binding = TranscriptBinding::Protocol {
+        proof,
+        protocol_id: ProtocolId::new(NAME, &(4usize, 100usize)),
+    };
+    assert!(matches!(
+        verify(binding, &ALL),
+        Err(ProofError::ProtocolMismatch)
+    ));
+}
+
+#[test]
+fn signatures_are_decoded_within_limits() {
+    let mut prover =
+        ProverState::<F, EF, _>::new_signing(sha256_challenger(), true, PUBLIC_KEY, MESSAGE);
+    respond(&mut prover);
+    let signature = prover.into_signature();
+    let binding = |message: &'static [u8]| TranscriptBinding::Signature {
+        signature: signature.clone(),
+        public_key: PUBLIC_KEY,
+        message,
+    };
+
+    verify(binding(MESSAGE), &ALL).unwrap();
+    assert!(matches!(
+        verify(binding(b"other message"), &ALL),
+        Err(ProofError::InvalidProof)
+    ));
+
+    let limits = VerifierLimits {
+        max_proof_data_len: 8,
+        ..VerifierLimits::UNLIMITED
+    };
+    assert!(matches!(
+        AnyVerifierState::new(binding(MESSAGE), &ALL, limits),
+        Err(ProofError::LimitExceeded)
+    ));
+}
+
+#[test]
+fn finish_rejects_trailing_data() {
+    let mut proof = prove::<EF, _>(challenger());
+    proof.proof_data.push(F::ONE);
+    assert!(matches!(
+        verify(protocol(proof), &ALL),
+        Err(ProofError::InvalidProof)
+    ));
+}
@@ -0,0 +1,62 @@
+//! Encoding of bytes as field elements, and its use by `observe_bytes`.
+
+mod common;
+
+use std::collections::HashSet;
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::PrimeCharacteristicRing;
+
+#[test]
+fn encoding_is_injective() {
+    // All byte strings of up to 4 bytes over {0, 1, 255}, so that zero-padded chunks and
+    // trailing zeros are covered.
+    let mut inputs = vec![Vec::new()];
+    for len in 1..=4 {
+        let previous = inputs.clone();
+        inputs.extend(
+            previous
+                .iter()
+                .filter(|bytes| bytes.len() == len - 1)
+                .flat_map(|bytes| [0u8, 1, 255].map(|byte| [bytes.as_slice(), &[byte]].concat())),
+        );
+    }
+    let encodings = inputs
+        .iter()
+        .map(|bytes| bytes_to_field_elements::<F>(bytes))
+        .collect::<HashSet<_>>();
+    assert_eq!(encodings.len(), inputs.len());
+}
+
+#[test]
+fn long_inputs_are_length_prefixed() {
+    let bytes = vec![0u8; 1 << 24];
+    let encoding = bytes_to_field_elements::<F>(&bytes);
+    assert_eq!(encoding[..3], [F::ZERO, F::ONE, F::ZERO]);
+    assert_eq!(
+        encoding.len(),
+        3 + (1 << 24).div_ceil(BYTES_PER_FIELD_ELEMENT)
+    );
+    assert!(encoding[3..].iter().all(|&x| x == F::ZERO));
+}
+
+#[test]
+fn observe_bytes_mirrors_prover_and_verifier() {
+    let prove = |bytes: &[u8]| {
+        let mut prover = ProverState::<F, EF, _>::new(challenger(), false);
+        prover.observe_bytes(bytes);
+        let challenge = prover.sample();
+        (prover.into_proof(), challenge)
+    };
+    let (proof, challenge) = prove(b"abc");
+    assert!(proof.proof_data.is_empty());
+
+    let mut verifier = VerifierState::<F, EF, _>::new(proof, challenger());
+    verifier.observe_bytes(b"abc");
+    assert_eq!(verifier.sample(), challenge);
+    verifier.finish().unwrap();
+
+    assert_ne!(prove(b"abc\0").1, challenge);
+    assert_ne!(prove(b"").1, prove(b"\0").1);
+}
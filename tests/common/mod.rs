@@ -0,0 +1,56 @@
+//! Fixture shared by the integration tests.
+#![allow(dead_code)]
+
+use fiat_shamir::*;
+use p3_challenger::{DuplexChallenger, FieldChallenger, GrindingChallenger};
+use p3_field::{PrimeCharacteristicRing, extension::BinomialExtensionField};
+use p3_koala_bear::{KoalaBear, Poseidon2KoalaBear, default_koalabear_poseidon2_16};
+
+pub type F = KoalaBear;
+pub type EF = BinomialExtensionField<F, 4>;
+pub type Challenger = DuplexChallenger<F, Poseidon2KoalaBear<16>, 16, 8>;
+
+pub fn challenger() -> Challenger {
+    DuplexChallenger::new(default_koalabear_poseidon2_16())
+}
+
+pub const GRINDING_BITS: usize = 4;
+
+/// Prover side of a small protocol using every kind of transcript operation, under two labels.
+pub fn prove_mixed<C>(prover: &mut ProverState<F, EF, C>)
+where
+    C: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
+{
+    prover.set_label("commit");
+    prover.add_base_scalars(&[F::ONE, F::TWO, F::from_u32(3)]);
+    prover.add_extension_scalars(&[EF::from_u32(4), EF::from_u32(5)]);
+    prover.add_commitment(Digest([F::from_u32(6); DEFAULT_DIGEST_LEN]));
+    prover.sample();
+    prover.set_label("open");
+    prover.hint_base_scalars(&[F::from_u32(7); LEAN_ISA_VECTOR_LEN]);
+    prover.hint_merkle_path(vec![Digest([F::from_u32(8); DEFAULT_DIGEST_LEN]); 2]);
+    prover.pow_grinding(GRINDING_BITS);
+    prover.sample_bits(5);
+    prover.clear_label();
+    prover.add_extension_scalar(EF::from_u32(9));
+}
+
+/// Verifier side of [`prove_mixed`].
+pub fn verify_mixed<C>(verifier: &mut VerifierState<F, EF, C>) -> ProofResult<()>
+where
+    C: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
+{
+    verifier.set_label("commit");
+    verifier.next_base_scalars_vec(3)?;
+    verifier.next_extension_scalars_vec(2)?;
+    verifier.next_commitment()?;
+    verifier.sample();
+    verifier.set_label("open");
+    verifier.receive_hint_base_scalars(LEAN_ISA_VECTOR_LEN)?;
+    verifier.receive_hint_merkle_path()?;
+    verifier.check_pow_grinding(GRINDING_BITS)?;
+    verifier.sample_bits(5);
+    verifier.clear_label();
+    verifier.next_extension_scalar()?;
+    Ok(())
+}
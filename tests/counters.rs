@@ -0,0 +1,57 @@
+//! Transcript counters of a counting challenger, checked against a hand-computed schedule.
+
+use fiat_shamir::*;
+use p3_challenger::DuplexChallenger;
+use p3_field::{PrimeCharacteristicRing, extension::BinomialExtensionField};
+use p3_koala_bear::{KoalaBear, default_koalabear_poseidon2_16};
+
+type F = KoalaBear;
+type EF = BinomialExtensionField<F, 4>;
+
+fn counting_challenger() -> KoalaBearCountingChallenger {
+    DuplexChallenger::new(CountingPermutation::new(default_koalabear_poseidon2_16()))
+}
+
+fn scalars(n: u32) -> Vec<F> {
+    (0..n).map(F::from_u32).collect()
+}
+
+/// With a rate of 8, the sponge permutes once the 8th element is observed, and a challenge drawn
+/// right after reuses that output. The 3 elements observed next are only absorbed by the
+/// permutation preceding the second challenge.
+#[test]
+fn permutation_count_matches_duplex_schedule() {
+    let mut prover = ProverState::<F, EF, _>::new(counting_challenger(), false);
+    prover.set_label("absorb");
+    prover.add_base_scalars(&scalars(8));
+    prover.set_label("squeeze");
+    let first = prover.sample();
+    prover.add_base_scalars(&scalars(3));
+    let second = prover.sample();
+
+    let counters = prover.counters().clone();
+    assert_eq!(prover.challenger().permutation.count(), 2);
+    assert_eq!(
+        counters.total,
+        TranscriptCounts {
+            permutations: 2,
+            observed: 11,
+            squeezed: 8,
+            grinding_attempts: 0,
+        }
+    );
+    assert_eq!(counters.by_label["absorb"].permutations, 1);
+    assert_eq!(counters.by_label["squeeze"].permutations, 1);
+    assert_eq!(counters.by_label["squeeze"].observed, 3);
+
+    // The verifier performs the same permutations.
+    let mut verifier = VerifierState::<F, EF, _>::new(prover.into_proof(), counting_challenger());
+    verifier.set_label("absorb");
+    verifier.next_base_scalars_vec(8).unwrap();
+    verifier.set_label("squeeze");
+    assert_eq!(verifier.sample(), first);
+    verifier.next_base_scalars_vec(3).unwrap();
+    assert_eq!(verifier.sample(), second);
+    assert_eq!(verifier.counters(), &counters);
+    verifier.finish().unwrap();
+}
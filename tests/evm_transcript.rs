@@ -0,0 +1,96 @@
+//! Tests of the EVM transcript: known answers computed with an independent Keccak-256
+//! implementation, and differential test against the reference on-chain derivation.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_challenger::{CanObserve, CanSample, CanSampleBits};
+use p3_field::{PrimeCharacteristicRing, PrimeField32};
+
+#[test]
+fn evm_challenger_known_answers() {
+    let mut challenger = KoalaBearEvmChallenger::new();
+    let mut challenges = Vec::new();
+    for x in [1, 2, 3] {
+        challenger.observe(F::from_u32(x));
+    }
+    challenges.push(u64::from(challenger.sample().as_canonical_u32()));
+    challenges.push(u64::from(challenger.sample().as_canonical_u32()));
+    challenges.push(challenger.sample_bits(20) as u64);
+    challenger.observe(F::NEG_ONE);
+    challenges.push(challenger.sample_bits(31) as u64);
+    challenges.push(u64::from(challenger.sample().as_canonical_u32()));
+
+    let expected = [
+        164_321_922,
+        1_091_736_564,
+        317_716,
+        689_053_357,
+        275_955_755,
+    ];
+    assert_eq!(challenges, expected);
+
+    let calls = [
+        EvmTranscriptCall::Absorb([1, 2, 3].map(|x| evm_word(F::from_u32(x))).to_vec()),
+        EvmTranscriptCall::Squeeze,
+        EvmTranscriptCall::Squeeze,
+        EvmTranscriptCall::SqueezeBits(20),
+        EvmTranscriptCall::Absorb(vec![evm_word(F::NEG_ONE)]),
+        EvmTranscriptCall::SqueezeBits(31),
+        EvmTranscriptCall::Squeeze,
+    ];
+    assert_eq!(evm_reference_transcript(F::ORDER_U32, &calls), expected);
+}
+
+#[test]
+fn calldata_selector() {
+    // keccak256("verify(uint256[],uint256[],uint256[][])")[..4]
+    let calldata = encode_calldata(&Proof::<F>::default());
+    assert_eq!(calldata[..4], [0xc0, 0x12, 0xd0, 0x02]);
+}
+
+#[test]
+fn evm_profile_rejects_padding() {
+    let mut prover = ProverState::<F, EF, _>::new(KoalaBearEvmChallenger::new(), true);
+    prover.add_base_scalars(&[F::ONE]);
+    assert!(matches!(
+        EvmVerifier::<EF>::new_evm(prover.into_proof()),
+        Err(ProofError::PaddingMismatch)
+    ));
+
+    let mut prover = EvmProver::<EF>::new_evm();
+    prover.add_base_scalars(&[F::ONE]);
+    let mut verifier = EvmVerifier::<EF>::new_evm(prover.into_proof()).unwrap();
+    assert_eq!(verifier.next_base_scalars_vec(1).unwrap(), [F::ONE]);
+    verifier.finish().unwrap();
+}
+
+#[test]
+fn evm_challenger_matches_reference() {
+    let mut challenger = KoalaBearEvmChallenger::new();
+    let mut calls = Vec::new();
+    let mut challenges = Vec::new();
+
+    for round in 0..20u32 {
+        let message = (0..round % 5)
+            .map(|i| F::from_u32(round * 1_000_003 + i))
+            .collect::<Vec<_>>();
+        for &x in &message {
+            challenger.observe(x);
+        }
+        calls.push(EvmTranscriptCall::Absorb(
+            message.into_iter().map(evm_word).collect(),
+        ));
+
+        for _ in 0..round % 3 {
+            challenges.push(u64::from(challenger.sample().as_canonical_u32()));
+            calls.push(EvmTranscriptCall::Squeeze);
+        }
+        let bits = (round % 25) as usize;
+        challenges.push(challenger.sample_bits(bits) as u64);
+        calls.push(EvmTranscriptCall::SqueezeBits(bits));
+    }
+
+    assert_eq!(challenges, evm_reference_transcript(F::ORDER_U32, &calls));
+}
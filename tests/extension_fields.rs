@@ -0,0 +1,44 @@
+//! Scalars and challenges in an extension field other than the transcript's `EF`.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::{BasedVectorSpace, PrimeCharacteristicRing, extension::BinomialExtensionField};
+
+type EF8 = BinomialExtensionField<F, 8>;
+
+fn element<E: BasedVectorSpace<F>>(offset: u32) -> E {
+    E::from_basis_coefficients_fn(|i| F::from_u32(offset + i as u32))
+}
+
+#[test]
+fn mixed_degrees_with_padding() {
+    let a: EF = element(1);
+    let b: EF8 = element(10);
+
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), true);
+    prover.add_scalars_in(&[a]);
+    prover.add_scalars_in(&[b, b]);
+    let r8 = prover.sample_in::<EF8>();
+    let r4 = prover.sample_in::<EF>();
+    prover.add_scalars_in(&[r8]);
+    let proof = prover.into_proof();
+
+    // The degree-4 scalar is padded to the vector length, the degree-8 ones fill it exactly.
+    assert_eq!(proof.proof_data.len(), 4 * LEAN_ISA_VECTOR_LEN);
+    assert!(
+        proof.proof_data[4..LEAN_ISA_VECTOR_LEN]
+            .iter()
+            .all(|&x| x == F::ZERO)
+    );
+    assert_eq!(proof.proof_size, 4 + 3 * 8);
+
+    let mut verifier = VerifierState::<F, EF, _>::new(proof, challenger());
+    assert_eq!(verifier.next_scalars_in::<EF>(1).unwrap(), [a]);
+    assert_eq!(verifier.next_scalars_in::<EF8>(2).unwrap(), [b, b]);
+    assert_eq!(verifier.sample_in::<EF8>(), r8);
+    assert_eq!(verifier.sample_in::<EF>(), r4);
+    assert_eq!(verifier.next_scalars_in::<EF8>(1).unwrap(), [r8]);
+    verifier.finish().unwrap();
+}
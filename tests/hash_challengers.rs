@@ -0,0 +1,49 @@
+//! Prove/verify round trips over the byte hash challengers.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_challenger::{FieldChallenger, GrindingChallenger};
+
+fn round_trip<C>(challenger: fn() -> C, kind: ChallengerKind)
+where
+    C: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState + Clone,
+{
+    for padding in [false, true] {
+        let mut prover = ProverState::<F, EF, _>::new(challenger(), padding);
+        prove_mixed(&mut prover);
+        let proof = prover.into_proof();
+        assert_eq!(proof.config.challenger, Some(kind));
+        assert_eq!(proof.config.extension_degree, 4);
+
+        let mut verifier = VerifierState::<F, EF, _>::new(proof, challenger());
+        verify_mixed(&mut verifier).unwrap();
+        verifier.finish().unwrap();
+    }
+}
+
+#[test]
+fn keccak_round_trip() {
+    round_trip(keccak_challenger, ChallengerKind::KeccakKoalaBear);
+}
+
+#[test]
+fn sha256_round_trip() {
+    round_trip(sha256_challenger, ChallengerKind::Sha256KoalaBear);
+}
+
+#[test]
+fn blake3_round_trip() {
+    round_trip(blake3_challenger, ChallengerKind::Blake3KoalaBear);
+}
+
+#[test]
+fn poseidon2_kind_is_recorded() {
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), false);
+    prove_mixed(&mut prover);
+    assert_eq!(
+        prover.into_proof().config.challenger,
+        Some(ChallengerKind::Poseidon2KoalaBear)
+    );
+}
@@ -0,0 +1,114 @@
+//! Binding of hints into the transcript.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::PrimeCharacteristicRing;
+
+fn hints(offset: u32) -> Vec<F> {
+    (0..LEAN_ISA_VECTOR_LEN as u32)
+        .map(|i| F::from_u32(offset + i))
+        .collect()
+}
+
+fn tamper_advice(proof: &Proof<F>, index: usize) -> Proof<F> {
+    mutate_proof(proof, &ProofMutation::AdviceScalar { index })
+}
+
+/// Hints `hints(0)` (bound or not), then samples a challenge.
+fn prove_single_hint(bound: bool) -> (Proof<F>, EF) {
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), false);
+    if bound {
+        prover.bound_hint_base_scalars(&hints(0));
+    } else {
+        prover.hint_base_scalars(&hints(0));
+    }
+    let challenge = prover.sample();
+    (prover.into_proof(), challenge)
+}
+
+fn verify_single_hint(proof: Proof<F>, bound: bool) -> EF {
+    let mut verifier = VerifierState::<F, EF, _>::new(proof, challenger());
+    if bound {
+        verifier
+            .receive_bound_hint_base_scalars(LEAN_ISA_VECTOR_LEN)
+            .unwrap();
+    } else {
+        verifier
+            .receive_hint_base_scalars(LEAN_ISA_VECTOR_LEN)
+            .unwrap();
+    }
+    verifier.sample()
+}
+
+#[test]
+fn tampered_bound_hint_changes_next_challenge() {
+    let (proof, challenge) = prove_single_hint(true);
+    assert_eq!(verify_single_hint(proof.clone(), true), challenge);
+    assert_ne!(
+        verify_single_hint(tamper_advice(&proof, 0), true),
+        challenge
+    );
+
+    // Unbound hints do not affect the challenges.
+    let (proof, challenge) = prove_single_hint(false);
+    assert_eq!(
+        verify_single_hint(tamper_advice(&proof, 0), false),
+        challenge
+    );
+}
+
+#[test]
+fn binding_toggled_partway_through() {
+    let path = vec![Digest([F::from_u32(100); DEFAULT_DIGEST_LEN])];
+
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), false);
+    prover.hint_base_scalars(&hints(0));
+    let mut challenges = vec![prover.sample()];
+    prover.set_hint_binding(true);
+    prover.hint_base_scalars(&hints(10));
+    prover.hint_merkle_path(path.clone());
+    challenges.push(prover.sample());
+    prover.set_hint_binding(false);
+    prover.hint_base_scalars(&hints(20));
+    challenges.push(prover.sample());
+    let proof = prover.into_proof();
+
+    let verify = |proof: Proof<F>| {
+        let mut verifier = VerifierState::<F, EF, _>::new(proof, challenger());
+        verifier
+            .receive_hint_base_scalars(LEAN_ISA_VECTOR_LEN)
+            .unwrap();
+        let mut challenges = vec![verifier.sample()];
+        verifier.set_hint_binding(true);
+        verifier
+            .receive_hint_base_scalars(LEAN_ISA_VECTOR_LEN)
+            .unwrap();
+        verifier.receive_hint_merkle_path().unwrap();
+        challenges.push(verifier.sample());
+        verifier.set_hint_binding(false);
+        verifier
+            .receive_hint_base_scalars(LEAN_ISA_VECTOR_LEN)
+            .unwrap();
+        challenges.push(verifier.sample());
+        verifier.finish().unwrap();
+        challenges
+    };
+    assert_eq!(verify(proof.clone()), challenges);
+
+    // Only the hints sent while binding is enabled affect the challenges.
+    assert_eq!(verify(tamper_advice(&proof, 0)), challenges);
+    let tampered = verify(tamper_advice(&proof, LEAN_ISA_VECTOR_LEN));
+    assert_eq!(tampered[0], challenges[0]);
+    assert_ne!(tampered[1], challenges[1]);
+    let tampered = verify(mutate_proof(
+        &proof,
+        &ProofMutation::MerkleHint { path: 0, node: 0 },
+    ));
+    assert_ne!(tampered[1], challenges[1]);
+    assert_eq!(
+        verify(tamper_advice(&proof, 2 * LEAN_ISA_VECTOR_LEN)),
+        challenges
+    );
+}
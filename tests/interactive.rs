@@ -0,0 +1,100 @@
+//! Interactive mode: in-process and socket sessions, and misbehaving provers.
+
+mod common;
+
+use std::{
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::PrimeCharacteristicRing;
+use rand::{SeedableRng, rngs::StdRng};
+
+fn rng() -> StdRng {
+    StdRng::seed_from_u64(0)
+}
+
+#[test]
+fn run_interactive_round_trip() {
+    for padding in [false, true] {
+        run_interactive::<F, EF, _, _, _, DEFAULT_DIGEST_LEN, LEAN_ISA_VECTOR_LEN>(
+            rng(),
+            padding,
+            prove_mixed,
+            verify_mixed,
+        )
+        .unwrap();
+    }
+
+    // A verifier drawing one more challenge than the prover does not follow the transcript.
+    let result = run_interactive::<F, EF, _, _, _, DEFAULT_DIGEST_LEN, LEAN_ISA_VECTOR_LEN>(
+        rng(),
+        false,
+        prove_mixed,
+        |verifier| {
+            verify_mixed(verifier)?;
+            verifier.sample();
+            Ok(())
+        },
+    );
+    assert!(matches!(result, Err(ProofError::InvalidProof)));
+}
+
+#[test]
+fn misbehaving_prover_ends_the_session() {
+    let misbehaviours = [
+        InteractiveMessage::Challenge(F::ONE),
+        InteractiveMessage::ChallengeBits(1),
+        InteractiveMessage::SampleBitsRequest(usize::BITS as usize),
+    ];
+    for message in misbehaviours {
+        let (mut prover, verifier) = in_process_transports();
+        prover.send(InteractiveMessage::Observe(F::ONE));
+        prover.send(message);
+        let result = InteractiveVerifier::new(verifier, rng()).run();
+        assert!(matches!(result, Err(ProofError::InvalidProof)));
+        // The verifier hung up without answering.
+        assert_eq!(prover.recv(), None);
+    }
+
+    // An honest exchange is recorded in order.
+    let (mut prover, verifier) = in_process_transports();
+    prover.send(InteractiveMessage::Observe(F::ONE));
+    prover.send(InteractiveMessage::SampleBitsRequest(3));
+    drop(prover);
+    let transcript = InteractiveVerifier::new(verifier, rng()).run().unwrap();
+    assert_eq!(transcript.messages.len(), 3);
+    assert_eq!(transcript.messages[0], InteractiveMessage::Observe(F::ONE));
+    assert!(matches!(
+        transcript.messages[2],
+        InteractiveMessage::ChallengeBits(value) if value < 8
+    ));
+}
+
+#[test]
+fn socket_session() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let verifier = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        InteractiveVerifier::new(SocketTransport::new(stream), rng()).run()
+    });
+
+    let stream = TcpStream::connect(address).unwrap();
+    let mut prover = ProverState::<F, EF, _>::new(
+        InteractiveProverChallenger::new(SocketTransport::new(stream)),
+        true,
+    );
+    prove_mixed(&mut prover);
+    // Dropping the prover's challenger closes the connection, ending the session.
+    let proof = prover.into_proof();
+    let transcript = verifier.join().unwrap().unwrap();
+
+    let mut verifier =
+        VerifierState::<F, EF, _>::try_new(proof, ReplayChallenger::new(transcript)).unwrap();
+    verify_mixed(&mut verifier).unwrap();
+    assert!(verifier.challenger().is_consistent());
+    verifier.finish().unwrap();
+}
@@ -0,0 +1,296 @@
+//! Known-answer vectors for the KoalaBear / Poseidon2 transcript.
+//!
+//! The vectors pin down, for a fixed sequence of transcript operations, the exact challenges
+//! sampled and the exact `Proof` produced, in both padded and unpadded modes. Any change in the
+//! transcript (including a silent change in the underlying Plonky3 fork) makes the checker fail.
+//!
+//! To (re)generate the vectors after an intentional transcript change, bump [`KAT_VERSION`] and run:
+//!
+//! ```text
+//! cargo test --test known_answer -- --ignored generate_known_answer_vectors
+//! ```
+
+use std::{collections::VecDeque, fs, path::PathBuf};
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::{BasedVectorSpace, PrimeCharacteristicRing, PrimeField32};
+use serde::{Deserialize, Serialize};
+
+/// Version of the vector format and of the transcript script below.
+const KAT_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KnownAnswerFile {
+    version: u32,
+    vectors: Vec<KnownAnswerVector>,
+}
+
+/// Inputs fed to the transcript, as canonical `u32` representations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KnownAnswerInputs {
+    base_scalars: Vec<u32>,
+    extension_scalars: Vec<Vec<u32>>,
+    base_hints: Vec<u32>,
+    extension_hints: Vec<Vec<u32>>,
+    merkle_path: Vec<[u32; 8]>,
+    n_samples: usize,
+    grinding_bits: usize,
+    sample_bits: usize,
+}
+
+/// Expected outputs of the transcript, as canonical `u32` representations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KnownAnswerOutputs {
+    challenges: Vec<Vec<u32>>,
+    sampled_bits: usize,
+    proof_data: Vec<u32>,
+    advice: Vec<u32>,
+    merkle_hints: Vec<Vec<[u32; 8]>>,
+    proof_size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KnownAnswerVector {
+    padding: bool,
+    inputs: KnownAnswerInputs,
+    outputs: KnownAnswerOutputs,
+}
+
+fn vectors_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("vectors")
+        .join(format!("transcript_v{KAT_VERSION}.json"))
+}
+
+fn to_u32(x: F) -> u32 {
+    x.as_canonical_u32()
+}
+
+fn ef_to_u32(x: EF) -> Vec<u32> {
+    <EF as BasedVectorSpace<F>>::as_basis_coefficients_slice(&x)
+        .iter()
+        .copied()
+        .map(to_u32)
+        .collect()
+}
+
+fn ef_from_u32(coeffs: &[u32]) -> EF {
+    EF::from_basis_coefficients_iter(coeffs.iter().copied().map(F::from_u32)).unwrap()
+}
+
+fn default_inputs() -> KnownAnswerInputs {
+    let ext = |offset: u32| (0..4).map(|j| offset + j).collect::<Vec<u32>>();
+    KnownAnswerInputs {
+        base_scalars: (1..=5).collect(),
+        extension_scalars: vec![ext(100), ext(200), ext(300)],
+        base_hints: (1000..1008).collect(),
+        extension_hints: (0..8).map(|i| ext(2000 + 10 * i)).collect(),
+        merkle_path: (0..3)
+            .map(|i| std::array::from_fn(|j| 3000 + 8 * i + j as u32))
+            .collect(),
+        n_samples: 2,
+        grinding_bits: 4,
+        sample_bits: 16,
+    }
+}
+
+/// Run the fixed transcript script on the prover side, returning the observed outputs.
+fn run_prover(padding: bool, inputs: &KnownAnswerInputs) -> KnownAnswerOutputs {
+    let mut prover = FSProver::<EF, _>::new(challenger(), padding);
+    let mut challenges = Vec::new();
+
+    prover.add_base_scalars(
+        &inputs
+            .base_scalars
+            .iter()
+            .copied()
+            .map(F::from_u32)
+            .collect::<Vec<_>>(),
+    );
+    prover.add_extension_scalars(
+        &inputs
+            .extension_scalars
+            .iter()
+            .map(|c| ef_from_u32(c))
+            .collect::<Vec<_>>(),
+    );
+    challenges.push(prover.sample());
+
+    prover.hint_base_scalars(
+        &inputs
+            .base_hints
+            .iter()
+            .copied()
+            .map(F::from_u32)
+            .collect::<Vec<_>>(),
+    );
+    prover.hint_extension_scalars(
+        &inputs
+            .extension_hints
+            .iter()
+            .map(|c| ef_from_u32(c))
+            .collect::<Vec<_>>(),
+    );
+    prover.hint_merkle_path(
+        inputs
+            .merkle_path
+            .iter()
+            .map(|node| Digest(node.map(F::from_u32)))
+            .collect(),
+    );
+    challenges.extend(prover.sample_vec(inputs.n_samples));
+
+    prover.pow_grinding(inputs.grinding_bits);
+    let sampled_bits = prover.sample_bits(inputs.sample_bits);
+
+    let proof = prover.into_proof();
+    KnownAnswerOutputs {
+        challenges: challenges.into_iter().map(ef_to_u32).collect(),
+        sampled_bits,
+        proof_data: proof.proof_data.into_iter().map(to_u32).collect(),
+        advice: proof.advice.scalars.into_iter().map(to_u32).collect(),
+        merkle_hints: proof
+            .advice
+            .merkle_paths
+            .into_iter()
+            .map(|path| path.into_iter().map(|node| node.0.map(to_u32)).collect())
+            .collect(),
+        proof_size: proof.proof_size,
+    }
+}
+
+/// Replay the expected proof through the verifier and check it reproduces the same challenges.
+fn run_verifier(vector: &KnownAnswerVector) {
+    let inputs = &vector.inputs;
+    let outputs = &vector.outputs;
+    let proof = Proof {
+        proof_data: outputs
+            .proof_data
+            .iter()
+            .copied()
+            .map(F::from_u32)
+            .collect(),
+        padding: vector.padding,
+        vector_len: LEAN_ISA_VECTOR_LEN,
+        proof_size: outputs.proof_size,
+        advice: Advice {
+            scalars: outputs.advice.iter().copied().map(F::from_u32).collect(),
+            merkle_paths: outputs
+                .merkle_hints
+                .iter()
+                .map(|path| {
+                    path.iter()
+                        .map(|node| Digest(node.map(F::from_u32)))
+                        .collect()
+                })
+                .collect::<VecDeque<_>>(),
+        },
+        protocol_id: None,
+        config: ProofConfig {
+            challenger: Some(ChallengerKind::Poseidon2KoalaBear),
+            extension_degree: <EF as BasedVectorSpace<F>>::DIMENSION,
+        },
+    };
+    let mut verifier = FSVerifier::<EF, _>::new(proof, challenger());
+    let mut challenges = Vec::new();
+
+    let base_scalars = verifier
+        .next_base_scalars_vec(inputs.base_scalars.len())
+        .unwrap();
+    assert_eq!(
+        base_scalars.into_iter().map(to_u32).collect::<Vec<_>>(),
+        inputs.base_scalars
+    );
+    let extension_scalars = verifier
+        .next_extension_scalars_vec(inputs.extension_scalars.len())
+        .unwrap();
+    assert_eq!(
+        extension_scalars
+            .into_iter()
+            .map(ef_to_u32)
+            .collect::<Vec<_>>(),
+        inputs.extension_scalars
+    );
+    challenges.push(verifier.sample());
+
+    verifier
+        .receive_hint_base_scalars(inputs.base_hints.len())
+        .unwrap();
+    verifier
+        .receive_hint_extension_scalars(inputs.extension_hints.len())
+        .unwrap();
+    verifier.receive_hint_merkle_path().unwrap();
+    challenges.extend(verifier.sample_vec(inputs.n_samples));
+
+    verifier.check_pow_grinding(inputs.grinding_bits).unwrap();
+    assert_eq!(
+        verifier.sample_bits(inputs.sample_bits),
+        outputs.sampled_bits
+    );
+
+    assert_eq!(
+        challenges.into_iter().map(ef_to_u32).collect::<Vec<_>>(),
+        outputs.challenges
+    );
+    verifier.finish().unwrap();
+}
+
+fn generate() -> KnownAnswerFile {
+    let vectors = [false, true]
+        .into_iter()
+        .map(|padding| {
+            let inputs = default_inputs();
+            let outputs = run_prover(padding, &inputs);
+            KnownAnswerVector {
+                padding,
+                inputs,
+                outputs,
+            }
+        })
+        .collect();
+    KnownAnswerFile {
+        version: KAT_VERSION,
+        vectors,
+    }
+}
+
+#[test]
+#[ignore = "writes the known-answer vectors to disk"]
+fn generate_known_answer_vectors() {
+    let file = generate();
+    fs::create_dir_all(vectors_path().parent().unwrap()).unwrap();
+    fs::write(
+        vectors_path(),
+        serde_json::to_string_pretty(&file).unwrap() + "\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn check_known_answer_vectors() {
+    let path = vectors_path();
+    let content = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing {}: run `cargo test --test known_answer -- --ignored generate_known_answer_vectors`",
+            path.display()
+        )
+    });
+    let file: KnownAnswerFile = serde_json::from_str(&content).unwrap();
+    assert_eq!(file.version, KAT_VERSION);
+    assert!(file.vectors.iter().any(|v| v.padding));
+    assert!(file.vectors.iter().any(|v| !v.padding));
+
+    for vector in &file.vectors {
+        let outputs = run_prover(vector.padding, &vector.inputs);
+        assert_eq!(
+            outputs, vector.outputs,
+            "transcript changed (padding = {})",
+            vector.padding
+        );
+        run_verifier(vector);
+    }
+}
@@ -0,0 +1,24 @@
+//! Transcript layout recorded by the verifier matches the proof it consumed.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+
+#[test]
+fn padded_layout_matches_proof() {
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), true);
+    prove_mixed(&mut prover);
+    let proof = prover.into_proof();
+
+    let mut verifier = VerifierState::<F, EF, _>::new(proof.clone(), challenger());
+    verifier.record_layout();
+    verify_mixed(&mut verifier).unwrap();
+    let layout = verifier.take_layout().unwrap();
+    verifier.finish().unwrap();
+
+    assert!(layout.padding);
+    assert_eq!(layout.vector_len, LEAN_ISA_VECTOR_LEN);
+    assert_eq!(layout.proof_data_len(), proof.proof_data.len());
+    assert_eq!(layout.advice_len(), proof.advice.scalars.len());
+}
@@ -0,0 +1,165 @@
+//! Verifier resource limits, on the proof shape and on each read.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::PrimeCharacteristicRing;
+
+fn mixed_proof() -> Proof<F> {
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), true);
+    prove_mixed(&mut prover);
+    prover.into_proof()
+}
+
+/// The tightest limits accepting `proof`.
+fn exact_limits(proof: &Proof<F>) -> VerifierLimits {
+    let merkle_elements = proof
+        .advice
+        .merkle_paths
+        .iter()
+        .map(|path| path.len() * DEFAULT_DIGEST_LEN)
+        .sum::<usize>();
+    VerifierLimits {
+        max_proof_data_len: proof.proof_data.len(),
+        max_advice_len: proof.advice.scalars.len(),
+        max_merkle_hints: proof.advice.merkle_paths.len(),
+        max_merkle_path_depth: proof
+            .advice
+            .merkle_paths
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap(),
+        max_total_size: proof.proof_data.len() + proof.advice.scalars.len() + merkle_elements,
+    }
+}
+
+fn new_verifier(
+    proof: Proof<F>,
+    limits: VerifierLimits,
+) -> ProofResult<VerifierState<F, EF, Challenger>> {
+    VerifierState::new_with_limits(proof, challenger(), limits)
+}
+
+#[test]
+fn proof_shape_is_checked_before_verification() {
+    let proof = mixed_proof();
+    let limits = exact_limits(&proof);
+    let mut verifier = new_verifier(proof.clone(), limits).unwrap();
+    verify_mixed(&mut verifier).unwrap();
+    verifier.finish().unwrap();
+
+    let tighter = [
+        VerifierLimits {
+            max_proof_data_len: limits.max_proof_data_len - 1,
+            ..limits
+        },
+        VerifierLimits {
+            max_advice_len: limits.max_advice_len - 1,
+            ..limits
+        },
+        VerifierLimits {
+            max_merkle_hints: limits.max_merkle_hints - 1,
+            ..limits
+        },
+        VerifierLimits {
+            max_merkle_path_depth: limits.max_merkle_path_depth - 1,
+            ..limits
+        },
+        VerifierLimits {
+            max_total_size: limits.max_total_size - 1,
+            ..limits
+        },
+    ];
+    for limits in tighter {
+        assert!(matches!(
+            new_verifier(proof.clone(), limits),
+            Err(ProofError::LimitExceeded)
+        ));
+    }
+}
+
+#[test]
+fn oversized_reads_are_rejected() {
+    let proof = mixed_proof();
+    let limits = exact_limits(&proof);
+
+    // A read larger than the limit fails on the limit, before the transcript bounds.
+    let mut verifier = new_verifier(proof.clone(), limits).unwrap();
+    assert!(matches!(
+        verifier.next_base_scalars_vec(limits.max_proof_data_len + 1),
+        Err(ProofError::LimitExceeded)
+    ));
+
+    // Extension reads are limited by the number of elements they span, padding included.
+    let n_items = limits.max_proof_data_len / LEAN_ISA_VECTOR_LEN + 1;
+    assert!(n_items <= limits.max_proof_data_len);
+    let mut verifier = new_verifier(proof.clone(), limits).unwrap();
+    assert!(matches!(
+        verifier.next_extension_scalars_vec(n_items),
+        Err(ProofError::LimitExceeded)
+    ));
+    let mut verifier = new_verifier(proof.clone(), limits).unwrap();
+    assert!(matches!(
+        verifier.next_extension_scalars_vec(usize::MAX),
+        Err(ProofError::LimitExceeded)
+    ));
+
+    let mut verifier = new_verifier(proof, limits).unwrap();
+    assert!(matches!(
+        verifier.receive_hint_base_scalars(limits.max_advice_len + 1),
+        Err(ProofError::LimitExceeded)
+    ));
+    assert!(matches!(
+        verifier.receive_hint_extension_scalars(usize::MAX),
+        Err(ProofError::LimitExceeded)
+    ));
+}
+
+#[test]
+fn merkle_paths_at_the_depth_limit_are_accepted() {
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), false);
+    let node = Digest([F::ONE; DEFAULT_DIGEST_LEN]);
+    prover.hint_merkle_path(vec![node; 3]);
+    prover.hint_merkle_path(vec![node; 4]);
+    let proof = prover.into_proof();
+
+    let limits = VerifierLimits {
+        max_merkle_path_depth: 4,
+        ..VerifierLimits::UNLIMITED
+    };
+    let mut verifier = new_verifier(proof.clone(), limits).unwrap();
+    assert_eq!(verifier.receive_hint_merkle_path().unwrap().len(), 3);
+    assert_eq!(verifier.receive_hint_merkle_path().unwrap().len(), 4);
+    verifier.finish().unwrap();
+
+    let limits = VerifierLimits {
+        max_merkle_path_depth: 3,
+        ..limits
+    };
+    assert!(matches!(
+        new_verifier(proof, limits),
+        Err(ProofError::LimitExceeded)
+    ));
+}
+
+#[test]
+fn read_checks() {
+    let limits = VerifierLimits {
+        max_proof_data_len: 16,
+        max_advice_len: 8,
+        ..VerifierLimits::UNLIMITED
+    };
+    assert!(limits.check_read(16).is_ok());
+    assert!(matches!(
+        limits.check_read(17),
+        Err(ProofError::LimitExceeded)
+    ));
+    assert!(limits.check_advice_read(8).is_ok());
+    assert!(matches!(
+        limits.check_advice_read(9),
+        Err(ProofError::LimitExceeded)
+    ));
+    assert!(VerifierLimits::UNLIMITED.check_read(usize::MAX).is_ok());
+}
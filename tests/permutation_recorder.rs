@@ -0,0 +1,72 @@
+//! Attribution of the recorded permutations to the verifier's transcript operations.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_challenger::{CanObserve, CanSample};
+use p3_field::PrimeCharacteristicRing;
+use p3_koala_bear::default_koalabear_poseidon2_16;
+
+fn recorder() -> KoalaBearPermutationRecorder {
+    KoalaBearPermutationRecorder::new(default_koalabear_poseidon2_16())
+}
+
+fn commitment() -> Digest<F, DEFAULT_DIGEST_LEN> {
+    Digest(std::array::from_fn(|i| F::from_usize(i + 1)))
+}
+
+#[test]
+fn records_are_tagged_with_transcript_ops() {
+    let mut prover = ProverState::<F, EF, _>::new(recorder(), false);
+    prover.add_commitment(commitment());
+    prover.observe_public_base(&[F::ONE, F::TWO]);
+    let challenge = prover.sample();
+
+    let mut verifier = VerifierState::<F, EF, _>::new(prover.into_proof(), recorder());
+    verifier.set_label("commit");
+    assert_eq!(verifier.next_commitment().unwrap(), commitment());
+    verifier.set_label("challenge");
+    verifier.observe_public_base(&[F::ONE, F::TWO]);
+    assert_eq!(verifier.sample(), challenge);
+
+    // The commitment fills the rate, and the sample flushes the public inputs.
+    let records = verifier.challenger().permutation_records();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0].transcript_op,
+        Some(TranscriptOp::Observe {
+            offset: 0,
+            count: 1,
+            item_len: DEFAULT_DIGEST_LEN,
+            padded_lanes: 0,
+        })
+    );
+    assert_eq!(records[0].label.as_deref(), Some("commit"));
+    assert_eq!(
+        records[1].transcript_op,
+        Some(TranscriptOp::Sample {
+            count: 1,
+            item_len: 4
+        })
+    );
+    assert_eq!(records[1].label.as_deref(), Some("challenge"));
+}
+
+#[test]
+fn clones_record_independently() {
+    let mut original = recorder();
+    original.observe(F::ONE);
+    let _: F = original.sample();
+    let mut clone = original.clone();
+    assert_eq!(clone.permutation_records(), original.permutation_records());
+
+    clone.observe(F::TWO);
+    let _: F = clone.sample();
+    assert_eq!(original.permutation_records().len(), 1);
+    assert_eq!(clone.permutation_records().len(), 2);
+    assert_eq!(
+        clone.permutation_records()[0],
+        original.permutation_records()[0]
+    );
+}
@@ -0,0 +1,83 @@
+//! Binding of proofs to a protocol instance.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+
+const NAME: &str = "whir-v1";
+const PARAMS: (usize, usize) = (4, 128);
+
+fn prove(name: &str, params: (usize, usize)) -> (Proof<F>, EF) {
+    let mut prover = ProverState::<F, EF, _>::new_with_protocol(challenger(), true, name, &params);
+    let challenge = prover.sample();
+    prove_mixed(&mut prover);
+    (prover.into_proof(), challenge)
+}
+
+fn new_verifier(
+    proof: Proof<F>,
+    name: &str,
+    params: (usize, usize),
+    limits: VerifierLimits,
+) -> ProofResult<VerifierState<F, EF, Challenger>> {
+    VerifierState::new_with_protocol(proof, challenger(), name, &params, limits)
+}
+
+#[test]
+fn protocol_id_is_absorbed_and_verified() {
+    let (proof, challenge) = prove(NAME, PARAMS);
+    assert_eq!(proof.protocol_id, Some(ProtocolId::new(NAME, &PARAMS)));
+
+    let mut verifier =
+        new_verifier(proof.clone(), NAME, PARAMS, VerifierLimits::UNLIMITED).unwrap();
+    assert_eq!(verifier.sample(), challenge);
+    verify_mixed(&mut verifier).unwrap();
+    verifier.finish().unwrap();
+
+    // The identifier is absorbed before anything else: without it, the challenges differ.
+    let mut verifier = VerifierState::<F, EF, _>::new(proof, challenger());
+    assert_ne!(verifier.sample(), challenge);
+}
+
+#[test]
+fn other_protocols_are_rejected() {
+    let (proof, _) = prove(NAME, PARAMS);
+    for (name, params) in [("whir-v2", PARAMS), (NAME, (4, 100))] {
+        assert!(matches!(
+            new_verifier(proof.clone(), name, params, VerifierLimits::UNLIMITED),
+            Err(ProofError::ProtocolMismatch)
+        ));
+    }
+
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), true);
+    prove_mixed(&mut prover);
+    assert!(matches!(
+        new_verifier(prover.into_proof(), NAME, PARAMS, VerifierLimits::UNLIMITED),
+        Err(ProofError::ProtocolMismatch)
+    ));
+}
+
+#[test]
+fn limits_apply_to_bound_proofs() {
+    let (proof, _) = prove(NAME, PARAMS);
+    let limits = VerifierLimits {
+        max_proof_data_len: proof.proof_data.len() - 1,
+        ..VerifierLimits::UNLIMITED
+    };
+    assert!(matches!(
+        new_verifier(proof.clone(), NAME, PARAMS, limits),
+        Err(ProofError::LimitExceeded)
+    ));
+
+    // The limits also apply to each read.
+    let limits = VerifierLimits {
+        max_advice_len: proof.advice.scalars.len(),
+        ..VerifierLimits::UNLIMITED
+    };
+    let mut verifier = new_verifier(proof, NAME, PARAMS, limits).unwrap();
+    assert!(matches!(
+        verifier.receive_hint_base_scalars(limits.max_advice_len + 1),
+        Err(ProofError::LimitExceeded)
+    ));
+}
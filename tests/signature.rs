@@ -0,0 +1,93 @@
+//! Signatures of knowledge: the transcript binds the public key and the message.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_challenger::{FieldChallenger, GrindingChallenger};
+use p3_field::PrimeCharacteristicRing;
+
+const PUBLIC_KEY: &[u8] = b"public key";
+const MESSAGE: &[u8] = b"message";
+
+/// A toy signature: a commitment, then the challenge it yields echoed back. The verifier only
+/// accepts if it derives the same challenge, i.e. if the transcripts agree.
+fn respond<C>(prover: &mut ProverState<F, EF, C>)
+where
+    C: FieldChallenger<F> + GrindingChallenger<Witness = F> + ChallengerState,
+{
+    prover.add_base_scalars(&[F::ONE, F::TWO, F::from_u32(3)]);
+    let challenge = prover.sample();
+    prover.add_extension_scalar(challenge);
+}
+
+fn sign(public_key: &[u8], message: &[u8]) -> Signature<F> {
+    let mut prover = ProverState::<F, EF, _>::new_signing(challenger(), true, public_key, message);
+    respond(&mut prover);
+    prover.into_signature()
+}
+
+fn verify(signature: Signature<F>, public_key: &[u8], message: &[u8]) -> ProofResult<()> {
+    let mut verifier = VerifierState::<F, EF, _>::new_signature(
+        signature,
+        challenger(),
+        public_key,
+        message,
+        VerifierLimits::UNLIMITED,
+    )?;
+    verifier.next_base_scalars_vec(3)?;
+    let challenge = verifier.sample();
+    if verifier.next_extension_scalar()? != challenge {
+        return Err(ProofError::InvalidProof);
+    }
+    verifier.finish()
+}
+
+#[test]
+fn signature_round_trip() {
+    let signature = sign(PUBLIC_KEY, MESSAGE);
+    let json = serde_json::to_string(&signature).unwrap();
+    let decoded: Signature<F> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, signature);
+    verify(decoded, PUBLIC_KEY, MESSAGE).unwrap();
+}
+
+#[test]
+fn wrong_context_is_rejected() {
+    let signature = sign(PUBLIC_KEY, MESSAGE);
+    assert!(verify(signature.clone(), PUBLIC_KEY, b"other message").is_err());
+    assert!(verify(signature.clone(), b"other key", MESSAGE).is_err());
+    // The public key and the message are length-prefixed, so moving bytes between them
+    // changes the transcript.
+    assert!(verify(signature, b"public keymessage", b"").is_err());
+}
+
+#[test]
+fn plain_proof_is_not_a_signature() {
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), true);
+    respond(&mut prover);
+    let proof = prover.into_proof();
+    assert!(matches!(
+        verify(proof.into(), PUBLIC_KEY, MESSAGE),
+        Err(ProofError::InvalidProof)
+    ));
+}
+
+#[test]
+fn signature_limits_are_enforced() {
+    let signature = sign(PUBLIC_KEY, MESSAGE);
+    let limits = VerifierLimits {
+        max_proof_data_len: 8,
+        ..VerifierLimits::UNLIMITED
+    };
+    assert!(matches!(
+        VerifierState::<F, EF, _>::new_signature(
+            signature,
+            challenger(),
+            PUBLIC_KEY,
+            MESSAGE,
+            limits
+        ),
+        Err(ProofError::LimitExceeded)
+    ));
+}
@@ -0,0 +1,40 @@
+//! Proof size report: its totals match the proof actually produced.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+
+#[test]
+fn report_totals_match_proof_size() {
+    for padding in [false, true] {
+        let mut prover = ProverState::<F, EF, _>::new(challenger(), padding);
+        prove_mixed(&mut prover);
+        let report = prover.proof_size_report().clone();
+        assert_eq!(report.total.proof_size(), prover.proof_size());
+
+        let proof = prover.into_proof();
+        assert_eq!(report.total.proof_size(), proof.proof_size);
+        let merkle_elements = proof
+            .advice
+            .merkle_paths
+            .iter()
+            .map(|path| path.len() * DEFAULT_DIGEST_LEN)
+            .sum::<usize>();
+        assert_eq!(
+            report.total.total_elements(),
+            proof.proof_data.len() + proof.advice.scalars.len() + merkle_elements
+        );
+        assert_eq!(report.total.padding_zeros > 0, padding);
+
+        // Everything but the final, unlabeled extension scalar is attributed to a label.
+        let labeled = report
+            .by_label
+            .values()
+            .map(ProofSizeBreakdown::proof_size)
+            .sum::<usize>();
+        assert_eq!(labeled + 4, report.total.proof_size());
+        assert_eq!(report.by_label["open"].merkle_paths, 2 * DEFAULT_DIGEST_LEN);
+        assert_eq!(report.by_label["open"].grinding_witnesses, 1);
+    }
+}
@@ -0,0 +1,67 @@
+//! Compressed sumcheck rounds, written once for the prover and the verifier.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::{BasedVectorSpace, PrimeCharacteristicRing};
+
+const N_ROUNDS: u32 = 3;
+const DEGREE: usize = 2;
+
+/// Run `N_ROUNDS` sumcheck rounds from `claim`, returning the challenges and the final claim.
+fn sumcheck<T: Transcript<EF> + ?Sized>(
+    transcript: &mut T,
+    mut claim: EF,
+) -> ProofResult<(Vec<EF>, EF)> {
+    let mut challenges = Vec::new();
+    for round in 0..N_ROUNDS {
+        let (challenge, next_claim) = sumcheck_round(transcript, DEGREE, claim, || {
+            let c0 = EF::from_u32(round + 1);
+            let c2 = EF::from_u32(7 * round + 3);
+            vec![c0, claim - c0.double() - c2, c2]
+        })?;
+        challenges.push(challenge);
+        claim = next_claim;
+    }
+    Ok((challenges, claim))
+}
+
+fn prove() -> (Proof<F>, (Vec<EF>, EF)) {
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), false);
+    let output = sumcheck(&mut prover, EF::from_u32(42)).unwrap();
+    (prover.into_proof(), output)
+}
+
+#[test]
+fn prove_verify_round_trip() {
+    let (proof, output) = prove();
+    assert_eq!(
+        proof.proof_data.len(),
+        N_ROUNDS as usize * DEGREE * <EF as BasedVectorSpace<F>>::DIMENSION
+    );
+
+    let mut verifier = VerifierState::<F, EF, _>::new(proof.clone(), challenger());
+    assert_eq!(sumcheck(&mut verifier, EF::from_u32(42)).unwrap(), output);
+    verifier.finish().unwrap();
+
+    // Same rounds through the dyn transcripts.
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), false);
+    let dyn_prover: &mut dyn ProverTranscript<EF> = &mut prover;
+    assert_eq!(sumcheck(dyn_prover, EF::from_u32(42)).unwrap(), output);
+    assert_eq!(prover.into_proof(), proof);
+
+    let mut verifier = VerifierState::<F, EF, _>::new(proof, challenger());
+    let dyn_verifier: &mut dyn VerifierTranscript<EF> = &mut verifier;
+    assert_eq!(sumcheck(dyn_verifier, EF::from_u32(42)).unwrap(), output);
+}
+
+#[test]
+fn tampered_coefficient_changes_the_claim() {
+    let (proof, (_, final_claim)) = prove();
+    let tampered = mutate_proof(&proof, &ProofMutation::ProofData { index: 0 });
+
+    let mut verifier = VerifierState::<F, EF, _>::new(tampered, challenger());
+    let (_, tampered_claim) = sumcheck(&mut verifier, EF::from_u32(42)).unwrap();
+    assert_ne!(tampered_claim, final_claim);
+}
@@ -0,0 +1,48 @@
+//! Tamper test of a small protocol: every mutation of an honest proof must be rejected.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::PrimeCharacteristicRing;
+
+/// The prover sends three scalars `x`, then `r * (x0 + x1 + x2)` for a challenge `r`, and hints
+/// the sum and a one-node merkle path opening `x0`.
+fn prove(prover: &mut ProverState<F, EF, Challenger>) {
+    let x = [F::from_u32(1), F::from_u32(2), F::from_u32(3)];
+    let sum = x.iter().copied().sum::<F>();
+    prover.add_base_scalars(&x);
+    let r = prover.sample();
+    prover.add_extension_scalar(r * sum);
+    prover.hint_base_scalars(&[sum; LEAN_ISA_VECTOR_LEN]);
+    prover.hint_merkle_path(vec![Digest([x[0]; DEFAULT_DIGEST_LEN])]);
+}
+
+fn verify(verifier: &mut VerifierState<F, EF, Challenger>) -> ProofResult<()> {
+    let x = verifier.next_base_scalars_const::<3>()?;
+    let sum = x.iter().copied().sum::<F>();
+    let r = verifier.sample();
+    if verifier.next_extension_scalar()? != r * sum {
+        return Err(ProofError::InvalidProof);
+    }
+    if verifier.receive_hint_base_scalars(LEAN_ISA_VECTOR_LEN)? != [sum; LEAN_ISA_VECTOR_LEN] {
+        return Err(ProofError::InvalidProof);
+    }
+    if verifier.receive_hint_merkle_path()? != [Digest([x[0]; DEFAULT_DIGEST_LEN])] {
+        return Err(ProofError::InvalidProof);
+    }
+    Ok(())
+}
+
+#[test]
+fn every_mutation_is_rejected() {
+    for padding in [false, true] {
+        let report = find_accepted_mutations(challenger(), padding, prove, verify);
+        assert!(report.n_mutations > 0);
+        assert!(
+            report.all_rejected(),
+            "padding: {padding}, accepted: {:?}",
+            report.accepted
+        );
+    }
+}
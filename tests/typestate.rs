@@ -0,0 +1,66 @@
+//! Typestate protocols: the same spec drives the prover and the verifier.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::PrimeCharacteristicRing;
+
+const N_HINTS: usize = LEAN_ISA_VECTOR_LEN;
+
+type Protocol = SendBase<
+    3,
+    SampleExtension<
+        2,
+        SendExtension<1, HintBase<N_HINTS, Grind<GRINDING_BITS, SampleBits<5, Done>>>>,
+    >,
+>;
+
+fn hints() -> [F; N_HINTS] {
+    std::array::from_fn(F::from_usize)
+}
+
+#[test]
+fn typed_round_trip() {
+    for padding in [false, true] {
+        let prover =
+            TypedProver::<_, Protocol>::new(ProverState::<F, EF, _>::new(challenger(), padding));
+        let (challenges, prover) = prover.send([F::ONE, F::TWO, F::from_u32(3)]).sample();
+        let (bits, prover) = prover
+            .send([challenges[0] * challenges[1]])
+            .hint(hints())
+            .grind()
+            .sample_bits();
+        let proof = prover.finish().into_proof();
+
+        let verifier =
+            TypedVerifier::<_, Protocol>::new(VerifierState::<F, EF, _>::new(proof, challenger()));
+        let (scalars, verifier) = verifier.receive().unwrap();
+        assert_eq!(scalars, [F::ONE, F::TWO, F::from_u32(3)]);
+        let (verifier_challenges, verifier) = verifier.sample().unwrap();
+        assert_eq!(verifier_challenges, challenges);
+        let ([product], verifier) = verifier.receive().unwrap();
+        assert_eq!(product, challenges[0] * challenges[1]);
+        let (verifier_hints, verifier) = verifier.receive_hint().unwrap();
+        assert_eq!(verifier_hints, hints());
+        let (verifier_bits, verifier) = verifier.check_grinding().unwrap().sample_bits().unwrap();
+        assert_eq!(verifier_bits, bits);
+        verifier.finish().unwrap();
+    }
+}
+
+#[test]
+fn typed_finish_rejects_trailing_data() {
+    let prover =
+        TypedProver::<_, SendBase<1, Done>>::new(ProverState::<F, EF, _>::new(challenger(), false));
+    let mut state = prover.send([F::ONE]).finish();
+    state.add_base_scalars(&[F::TWO]);
+    let proof = state.into_proof();
+
+    let verifier = TypedVerifier::<_, SendBase<1, Done>>::new(VerifierState::<F, EF, _>::new(
+        proof,
+        challenger(),
+    ));
+    let (_, verifier) = verifier.receive().unwrap();
+    assert!(matches!(verifier.finish(), Err(ProofError::InvalidProof)));
+}
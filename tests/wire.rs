@@ -0,0 +1,163 @@
+//! Wire encoding of proofs: round trip and rejection of malformed encodings.
+
+mod common;
+
+use common::*;
+use fiat_shamir::*;
+use p3_field::{BasedVectorSpace, PrimeCharacteristicRing};
+
+fn base(values: &[u32]) -> Vec<F> {
+    values.iter().copied().map(F::from_u32).collect()
+}
+
+fn extension(offset: u32) -> EF {
+    EF::from_basis_coefficients_fn(|i| F::from_u32(offset + i as u32))
+}
+
+fn commitment() -> Digest<F, DEFAULT_DIGEST_LEN> {
+    Digest(std::array::from_fn(|i| F::from_usize(100 + i)))
+}
+
+/// A padded proof mixing base scalars, extension scalars, a commitment and grinding.
+fn padded_proof() -> Proof<F> {
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), true);
+    prover.add_base_scalars(&base(&[1, 2, 3]));
+    prover.add_extension_scalars(&[extension(10), extension(20)]);
+    prover.add_commitment(commitment());
+    prover.sample();
+    prover.pow_grinding(GRINDING_BITS);
+    prover.add_base_scalars(&base(&[4]));
+    prover.add_extension_scalar(extension(30));
+    prover.into_proof()
+}
+
+fn verify(proof: Proof<F>) -> ProofResult<()> {
+    let mut verifier = VerifierState::<F, EF, _>::try_new(proof, challenger())?;
+    assert_eq!(verifier.next_base_scalars_vec(3)?, base(&[1, 2, 3]));
+    assert_eq!(
+        verifier.next_extension_scalars_vec(2)?,
+        [extension(10), extension(20)]
+    );
+    assert_eq!(verifier.next_commitment()?, commitment());
+    verifier.sample();
+    verifier.check_pow_grinding(GRINDING_BITS)?;
+    assert_eq!(verifier.next_base_scalars_vec(1)?, base(&[4]));
+    assert_eq!(verifier.next_extension_scalar()?, extension(30));
+    verifier.finish()
+}
+
+fn decode(wire: WireProof<F>) -> ProofResult<Proof<F>> {
+    wire.into_proof(&VerifierLimits::UNLIMITED)
+}
+
+#[test]
+fn padded_round_trip() {
+    let proof = padded_proof();
+    let wire = proof.to_wire();
+    assert!(wire.proof_data.len() < proof.proof_data.len());
+
+    let json = serde_json::to_string(&wire).unwrap();
+    let decoded = decode(serde_json::from_str(&json).unwrap()).unwrap();
+    assert_eq!(decoded, proof);
+    verify(decoded).unwrap();
+}
+
+#[test]
+fn unpadded_round_trip() {
+    let mut prover = ProverState::<F, EF, _>::new(challenger(), false);
+    prover.add_base_scalars(&base(&[1, 0, 0]));
+    prover.add_extension_scalar(extension(10));
+    let proof = prover.into_proof();
+
+    let wire = proof.to_wire();
+    assert!(wire.chunk_lens.is_empty());
+    assert_eq!(decode(wire).unwrap(), proof);
+}
+
+#[test]
+fn malformed_encodings_are_rejected() {
+    let wire = padded_proof().to_wire();
+    let nonzero_chunk = wire.chunk_lens.iter().position(|&len| len > 0).unwrap();
+
+    let mut malformed = Vec::new();
+
+    let mut missing_chunk = wire.clone();
+    missing_chunk.chunk_lens.pop();
+    malformed.push(missing_chunk);
+
+    let mut extra_chunk = wire.clone();
+    extra_chunk.chunk_lens.push(0);
+    malformed.push(extra_chunk);
+
+    let mut oversized_chunk = wire.clone();
+    oversized_chunk.chunk_lens[0] = wire.vector_len as u8 + 1;
+    malformed.push(oversized_chunk);
+
+    let mut non_canonical = wire.clone();
+    let index = wire.chunk_lens[..=nonzero_chunk]
+        .iter()
+        .map(|&len| len as usize)
+        .sum::<usize>();
+    non_canonical.chunk_lens[nonzero_chunk] += 1;
+    non_canonical.proof_data.insert(index, F::ZERO);
+    malformed.push(non_canonical);
+
+    let mut trailing_data = wire.clone();
+    trailing_data.proof_data.push(F::ONE);
+    malformed.push(trailing_data);
+
+    let mut truncated_data = wire.clone();
+    truncated_data.proof_data.pop();
+    malformed.push(truncated_data);
+
+    let mut zero_vector_len = wire.clone();
+    zero_vector_len.vector_len = 0;
+    malformed.push(zero_vector_len);
+
+    let mut unpadded_with_chunks = wire.clone();
+    unpadded_with_chunks.padding = false;
+    malformed.push(unpadded_with_chunks);
+
+    let mut unpadded_wrong_len = padded_proof().to_wire();
+    unpadded_wrong_len.padding = false;
+    unpadded_wrong_len.chunk_lens.clear();
+    malformed.push(unpadded_wrong_len);
+
+    for wire in malformed {
+        assert!(matches!(decode(wire), Err(ProofError::InvalidProof)));
+    }
+
+    // The decoded length is checked against the limits before allocating.
+    let mut huge = wire.clone();
+    huge.proof_data_len = usize::MAX;
+    let limits = VerifierLimits {
+        max_proof_data_len: 1 << 20,
+        ..VerifierLimits::UNLIMITED
+    };
+    assert!(matches!(
+        huge.into_proof(&limits),
+        Err(ProofError::LimitExceeded)
+    ));
+}
+
+#[test]
+fn vector_len_mismatch_fails_without_panicking() {
+    let mut proof = padded_proof();
+    proof.vector_len = 2 * LEAN_ISA_VECTOR_LEN;
+
+    assert!(matches!(
+        VerifierState::<F, EF, _>::try_new(proof.clone(), challenger()),
+        Err(ProofError::PaddingMismatch)
+    ));
+
+    let mut verifier = VerifierState::<F, EF, _>::new(proof.clone(), challenger());
+    assert!(matches!(
+        verifier.next_base_scalars_vec(3),
+        Err(ProofError::PaddingMismatch)
+    ));
+    let verifier = VerifierState::<F, EF, _>::new(proof, challenger());
+    assert!(matches!(
+        verifier.finish(),
+        Err(ProofError::PaddingMismatch)
+    ));
+}